@@ -1,3 +1,7 @@
 mod client;
+mod commando;
+mod transport;
 
 pub use client::Client;
+pub use commando::Commando;
+pub use transport::Transport;
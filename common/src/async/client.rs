@@ -1,34 +1,345 @@
 //! Async Client support
 //!
-//! Support for connecting to JSONRPC servers over UNIX sockets asynchronously,
+//! Support for connecting to JSONRPC servers over UNIX or TCP sockets asynchronously,
 //! sending requests, and parsing responses using Tokio.
-use std::path::{Path, PathBuf};
+//!
+//! Every connection is served by a background task that reads the messages
+//! sent by lightningd and dispatches each response to the request with the
+//! same id, so many requests can be in flight on the same socket.
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, MutexGuard};
 use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::UnixStream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, UnixStream};
+use tokio::sync::{mpsc, oneshot, Mutex};
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
-use serde_json::Deserializer;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
+use crate::client::Endpoint;
 use crate::errors::Error;
-use crate::types::{Request, Response};
+use crate::retry::{is_idempotent, RetryPolicy};
+use crate::types::{BatchRequest, BatchResponse, Id, Notification, Request, Response};
 
 /// A handle to a remote JSONRPC server for async operations
 #[derive(Debug)]
 pub struct Client {
-    /// Path to the lightning-rpc socket file
-    sockpath: PathBuf,
+    /// Address of the lightning-rpc socket
+    endpoint: Endpoint,
     /// Timeout for RPC request
     timeout: Option<Duration>,
+    /// Keep the socket open between requests instead of connecting for each call
+    persistent: bool,
+    /// Connection shared by all the requests when running in persistent mode
+    conn: Mutex<Option<Arc<Connection>>>,
+    /// Counter used to generate the id of the next request
+    next_id: AtomicU64,
+    /// Channel receiving the notifications sent by lightningd, if enabled
+    notifications: Option<mpsc::UnboundedSender<Notification>>,
+    /// Policy used to retry the requests failing for a transient reason
+    retry: Option<RetryPolicy>,
+}
+
+/// Requests waiting for a response on a connection
+#[derive(Debug, Default)]
+struct Pending {
+    /// The connection can not be used anymore
+    closed: bool,
+    waiters: HashMap<Id, oneshot::Sender<Result<Value, Error>>>,
+}
+
+type SharedPending = Arc<StdMutex<Pending>>;
+
+fn lock(pending: &SharedPending) -> MutexGuard<'_, Pending> {
+    pending.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+type Reader = Box<dyn AsyncRead + Send + Unpin>;
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// An open connection to the lightning-rpc socket
+struct Connection {
+    writer: Mutex<Writer>,
+    pending: SharedPending,
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Connection {
+    /// Connect to the socket and spawn the task reading the responses.
+    async fn open(
+        endpoint: &Endpoint,
+        notifications: Option<mpsc::UnboundedSender<Notification>>,
+    ) -> Result<Connection, Error> {
+        let (reader, writer): (Reader, Writer) = match endpoint {
+            Endpoint::Unix(sockpath) => {
+                let (reader, writer) = UnixStream::connect(sockpath).await?.into_split();
+                (Box::new(reader), Box::new(writer))
+            }
+            Endpoint::Tcp(addr) => {
+                let (reader, writer) = TcpStream::connect(addr).await?.into_split();
+                (Box::new(reader), Box::new(writer))
+            }
+        };
+        let pending = SharedPending::default();
+        tokio::spawn(read_loop(reader, pending.clone(), notifications));
+        Ok(Connection {
+            writer: Mutex::new(writer),
+            pending,
+        })
+    }
+
+    fn is_closed(&self) -> bool {
+        lock(&self.pending).closed
+    }
+
+    /// Write the request on the socket, returning a handle to wait for its response.
+    async fn send<S: Serialize>(
+        &self,
+        id: &Id,
+        request: &Request<S>,
+    ) -> Result<PendingResponse, Error> {
+        let request_data = serde_json::to_vec(request)?;
+        let (sender, receiver) = oneshot::channel();
+        {
+            let mut pending = lock(&self.pending);
+            if pending.closed {
+                return Err(closed_error(io::ErrorKind::NotConnected));
+            }
+            pending.waiters.insert(id.clone(), sender);
+        }
+        let response = PendingResponse {
+            id: id.clone(),
+            pending: self.pending.clone(),
+            receiver,
+        };
+
+        let mut writer = self.writer.lock().await;
+        // if the write is interrupted halfway (e.g. by a timeout) the stream
+        // is left in an unknown state, so the connection is closed.
+        let mut interrupted = WriteGuard(Some(&self.pending));
+        let written = async {
+            writer.write_all(&request_data).await?;
+            writer.flush().await
+        }
+        .await;
+        interrupted.0 = None;
+        if let Err(err) = written {
+            let err = Error::from(err);
+            close_pending(&self.pending, &err);
+            return Err(err);
+        }
+        Ok(response)
+    }
+}
+
+/// Closes the connection when dropped while a write is in progress.
+struct WriteGuard<'a>(Option<&'a SharedPending>);
+
+impl Drop for WriteGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(pending) = self.0 {
+            close_pending(
+                pending,
+                &Error::Io(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "request interrupted while being written",
+                )),
+            );
+        }
+    }
+}
+
+/// A request written on the socket that is waiting for its response.
+///
+/// Dropping it before the response arrives forgets the request, the
+/// response will be discarded when it is received.
+struct PendingResponse {
+    id: Id,
+    pending: SharedPending,
+    receiver: oneshot::Receiver<Result<Value, Error>>,
+}
+
+impl PendingResponse {
+    async fn wait(mut self) -> Result<Value, Error> {
+        (&mut self.receiver)
+            .await
+            .unwrap_or_else(|_| Err(closed_error(io::ErrorKind::UnexpectedEof)))
+    }
+}
+
+impl Drop for PendingResponse {
+    fn drop(&mut self) {
+        lock(&self.pending).waiters.remove(&self.id);
+    }
+}
+
+fn closed_error(kind: io::ErrorKind) -> Error {
+    Error::Io(io::Error::new(kind, "connection closed by lightningd"))
+}
+
+/// Mark the connection as unusable, failing all the pending requests.
+fn close_pending(pending: &SharedPending, err: &Error) {
+    let mut pending = lock(pending);
+    pending.closed = true;
+    for (_, waiter) in pending.waiters.drain() {
+        let _ = waiter.send(Err(Error::Io(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            err.to_string(),
+        ))));
+    }
+}
+
+/// Read the messages sent by lightningd and dispatch them to the
+/// requests waiting for them, until the connection is closed.
+async fn read_loop(
+    mut reader: Reader,
+    pending: SharedPending,
+    notifications: Option<mpsc::UnboundedSender<Notification>>,
+) {
+    let mut buffer = MessageBuffer::default();
+    let mut chunk = [0; 4096];
+    let err = loop {
+        match buffer.next_message() {
+            Ok(Some(message)) => {
+                dispatch(&pending, notifications.as_ref(), message);
+                continue;
+            }
+            Ok(None) => {}
+            Err(err) => break err,
+        }
+        match reader.read(&mut chunk).await {
+            Ok(0) => break closed_error(io::ErrorKind::UnexpectedEof),
+            Ok(n) => buffer.extend(&chunk[..n]),
+            Err(err) => break err.into(),
+        }
+    };
+    close_pending(&pending, &err);
+}
+
+/// Send the message to the request with the same id, or to the
+/// notifications channel if it is a notification.
+fn dispatch(
+    pending: &SharedPending,
+    notifications: Option<&mpsc::UnboundedSender<Notification>>,
+    message: Value,
+) {
+    let Some(id) = message.get("id") else {
+        if let (Some(notifications), Ok(notification)) =
+            (notifications, Notification::deserialize(&message))
+        {
+            let _ = notifications.send(notification);
+        }
+        return;
+    };
+    let Ok(id) = Id::deserialize(id) else {
+        return;
+    };
+    if let Some(waiter) = lock(pending).waiters.remove(&id) {
+        let _ = waiter.send(Ok(message));
+    }
+}
+
+/// Splits the bytes read from the connection into JSON messages.
+///
+/// Every byte is scanned only once to find where a message ends, and the
+/// message is parsed once it is complete, so that a large response (e.g.
+/// `listchannels` on mainnet) read in many chunks costs linear time.
+#[derive(Default)]
+struct MessageBuffer {
+    buffer: Vec<u8>,
+    /// number of bytes of `buffer` already scanned
+    scanned: usize,
+    /// nesting depth of the objects and arrays of the message being scanned
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl MessageBuffer {
+    fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decode the first complete message, removing it from the buffer.
+    ///
+    /// Returns `None` if more bytes are needed to complete the message.
+    fn next_message(&mut self) -> Result<Option<Value>, Error> {
+        while self.scanned < self.buffer.len() {
+            let byte = self.buffer[self.scanned];
+            self.scanned += 1;
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'{' | b'[' => self.depth += 1,
+                _ if self.depth == 0 && byte.is_ascii_whitespace() => {}
+                _ if self.depth == 0 => {
+                    return Err(Error::Io(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "lightningd sent something else than a JSON object",
+                    )))
+                }
+                b'"' => self.in_string = true,
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        let message = serde_json::from_slice(&self.buffer[..self.scanned]);
+                        self.buffer.drain(..self.scanned);
+                        self.scanned = 0;
+                        return Ok(Some(message?));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if self.depth == 0 {
+            // only whitespace left
+            self.buffer.clear();
+            self.scanned = 0;
+        }
+        Ok(None)
+    }
 }
 
 impl Client {
     /// Creates a new async client using the path to the socket file and initializing the timeout field to None
     pub fn new<P: AsRef<Path>>(sockpath: P) -> Client {
+        Client::with_endpoint(Endpoint::Unix(sockpath.as_ref().to_path_buf()))
+    }
+
+    /// Creates a new async client connecting over TCP to `addr` (e.g. `127.0.0.1:9735`),
+    /// where the lightning-rpc socket is forwarded with tools like socat or ssh.
+    pub fn new_tcp(addr: &str) -> Client {
+        Client::with_endpoint(Endpoint::Tcp(addr.to_owned()))
+    }
+
+    fn with_endpoint(endpoint: Endpoint) -> Client {
         Client {
-            sockpath: sockpath.as_ref().to_path_buf(),
+            endpoint,
             timeout: None,
+            persistent: false,
+            conn: Mutex::new(None),
+            next_id: AtomicU64::new(0),
+            notifications: None,
+            retry: None,
         }
     }
 
@@ -37,107 +348,366 @@ impl Client {
         self.timeout = timeout;
     }
 
+    /// Set an optional policy to retry the requests failing for a transient
+    /// reason, see the `retry` module.
+    ///
+    /// The commands that can not be safely repeated (e.g. `pay`) and the
+    /// batches are never retried.
+    pub fn set_retry_policy(&mut self, retry: Option<RetryPolicy>) {
+        self.retry = retry;
+    }
+
+    /// The policy retrying the requests, if any
+    pub(crate) fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    /// Keep a single connection open and share it between all the requests,
+    /// which can be in flight at the same time.
+    ///
+    /// When lightningd restarts, the connection is transparently reopened
+    /// on the next request. A request left unanswered by the restart is sent
+    /// again only if it can be safely repeated (e.g. `getinfo`, not `pay`).
+    pub fn set_persistent(&mut self, persistent: bool) {
+        self.persistent = persistent;
+        if !persistent {
+            self.conn.get_mut().take();
+        }
+    }
+
+    /// Generate a new unique id for a request
+    fn next_id(&self) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        Id::Str(format!("cln4rust/async/{id}"))
+    }
+
+    /// Enable the notifications sent by lightningd while a command is
+    /// running (e.g. the progress of `pay`), returning the stream receiving them.
+    ///
+    /// Notifications are enabled on every new connection, so this works
+    /// best together with `set_persistent`.
+    pub fn subscribe_notifications(&mut self) -> mpsc::UnboundedReceiver<Notification> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.notifications = Some(sender);
+        self.conn.get_mut().take();
+        receiver
+    }
+
+    /// Open a new connection, enabling the notifications if requested
+    async fn connect(&self) -> Result<Connection, Error> {
+        let conn = Connection::open(&self.endpoint, self.notifications.clone()).await?;
+        if self.notifications.is_some() {
+            let id = self.next_id();
+            let request = Request {
+                method: "notifications".to_owned(),
+                params: json!({ "enable": true }),
+                id: Some(id.clone()),
+                jsonrpc: "2.0".to_owned(),
+                filter: None,
+            };
+            let message = conn.send(&id, &request).await?.wait().await?;
+            Response::<Value>::from_message(message)?.into_result()?;
+        }
+        Ok(conn)
+    }
+
+    /// Return the connection to use for the next request, (re)connecting if needed.
+    async fn connection(&self) -> Result<Arc<Connection>, Error> {
+        if !self.persistent {
+            return Ok(Arc::new(self.connect().await?));
+        }
+        let mut conn = self.conn.lock().await;
+        match conn.as_ref() {
+            Some(conn) if !conn.is_closed() => Ok(conn.clone()),
+            _ => {
+                let new_conn = Arc::new(self.connect().await?);
+                *conn = Some(new_conn.clone());
+                Ok(new_conn)
+            }
+        }
+    }
+
     /// Sends a request to a client asynchronously
     pub async fn send_request<S: Serialize, D: DeserializeOwned>(
         &self,
         method: &str,
         params: S,
     ) -> Result<Response<D>, Error> {
-        let mut stream = UnixStream::connect(&self.sockpath).await?;
-        if let Some(timeout) = self.timeout {
-            tokio::time::timeout(timeout, async {
-                // Serialize and send the request
-                let request = Request {
-                    method: method.to_owned(),
-                    params,
-                    id: Some("cln4rust/async/0".into()),
-                    jsonrpc: "2.0".to_owned(),
-                };
-                let request_data = serde_json::to_vec(&request)?;
-                stream.write_all(&request_data).await?;
-                stream.flush().await?;
-
-                // Read the response incrementally
-                let mut response_data = Vec::new();
-                let mut buffer = [0; 1024];
-                loop {
-                    let n = stream.read(&mut buffer).await?;
-                    if n == 0 {
-                        break;
-                    }
-                    response_data.extend_from_slice(&buffer[..n]);
-                    // Check if the response ends with double newline (\n\n)
-                    if response_data.len() >= 2
-                        && &response_data[response_data.len() - 2..] == b"\n\n"
-                    {
-                        break;
-                    }
-                }
+        self.send_request_with_filter(method, params, None).await
+    }
 
-                // Deserialize the response
-                let response: Response<D> = Deserializer::from_slice(&response_data)
-                    .into_iter()
-                    .next()
-                    .map_or(Err(Error::NoErrorOrResult), |res| Ok(res?))?;
-                if response
-                    .jsonrpc
-                    .as_ref()
-                    .map_or(false, |version| version != "2.0")
-                {
-                    return Err(Error::VersionMismatch);
-                }
+    /// Sends a request with a `filter` that trims the response to the
+    /// fields listed in it, see the `filter` module.
+    pub async fn send_request_with_filter<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let policy = match &self.retry {
+            Some(policy) if is_idempotent(method) => policy,
+            _ => return self.send_once(method, &params, filter).await,
+        };
+        let mut attempt = 1;
+        loop {
+            let response = self.send_once(method, &params, filter.clone()).await;
+            if !policy.should_retry_response(attempt, &response) {
+                return response;
+            }
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
 
-                Ok(response)
-            })
-            .await
-            .map_err(|_| {
-                Error::Io(std::io::Error::new(
-                    std::io::ErrorKind::TimedOut,
-                    "Request timed out",
-                ))
-            })?
-        } else {
-            // Serialize and send the request
-            let request = Request {
+    /// Sends a request only once, even if a retry policy is set, for the
+    /// commands that must not be repeated.
+    pub async fn send_request_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_once(method, params, None).await
+    }
+
+    pub(crate) async fn send_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let request = Request {
+            method: method.to_owned(),
+            params,
+            id: Some(self.next_id()),
+            jsonrpc: "2.0".to_owned(),
+            filter,
+        };
+        let message = self.exchange(&[request]).await?.remove(0);
+        Response::from_message(message)
+    }
+
+    /// Sends all the calls of the batch together, and waits for all their responses.
+    ///
+    /// The error returned by lightningd for one of the calls is reported in its
+    /// own response, the other calls of the batch are not affected.
+    pub async fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        let requests: Vec<_> = batch
+            .calls()
+            .iter()
+            .map(|(method, params)| Request {
                 method: method.to_owned(),
                 params,
-                id: Some("cln4rust/async/0".into()),
+                id: Some(self.next_id()),
                 jsonrpc: "2.0".to_owned(),
-            };
-            let request_data = serde_json::to_vec(&request)?;
-            stream.write_all(&request_data).await?;
-            stream.flush().await?;
-
-            // Read the response incrementally
-            let mut response_data = Vec::new();
-            let mut buffer = [0; 1024];
-            loop {
-                let n = stream.read(&mut buffer).await?;
-                if n == 0 {
-                    break;
+                filter: None,
+            })
+            .collect();
+        let responses = self
+            .exchange(&requests)
+            .await?
+            .into_iter()
+            .map(Response::from_message)
+            .collect::<Result<_, _>>()?;
+        Ok(BatchResponse::new(batch, responses))
+    }
+
+    /// Write the requests and wait for their responses, applying the timeout
+    async fn exchange<S: Serialize>(&self, requests: &[Request<S>]) -> Result<Vec<Value>, Error> {
+        if let Some(timeout) = self.timeout {
+            tokio::time::timeout(timeout, self.roundtrip(requests))
+                .await
+                .map_err(|_| {
+                    Error::Io(io::Error::new(io::ErrorKind::TimedOut, "Request timed out"))
+                })?
+        } else {
+            self.roundtrip(requests).await
+        }
+    }
+
+    /// Write the requests and wait for their responses
+    async fn roundtrip<S: Serialize>(&self, requests: &[Request<S>]) -> Result<Vec<Value>, Error> {
+        let mut conn = self.connection().await?;
+        let mut pending = Vec::with_capacity(requests.len());
+        for request in requests {
+            let id = request.id.as_ref().ok_or(Error::NoErrorOrResult)?;
+            match conn.send(id, request).await {
+                Ok(response) => pending.push(response),
+                Err(Error::Io(_)) if self.persistent && pending.is_empty() => {
+                    // lightningd closed the socket (e.g. it was restarted),
+                    // so the request never reached it and we can try again.
+                    conn = self.connection().await?;
+                    pending.push(conn.send(id, request).await?);
                 }
-                response_data.extend_from_slice(&buffer[..n]);
-                // Check if the response ends with double newline (\n\n)
-                if response_data.len() >= 2 && &response_data[response_data.len() - 2..] == b"\n\n"
+                Err(err) => return Err(err),
+            }
+        }
+        let mut responses = Vec::with_capacity(pending.len());
+        for response in pending {
+            match response.wait().await {
+                Ok(response) => responses.push(response),
+                Err(Error::Io(_))
+                    if self.persistent
+                        && responses.is_empty()
+                        && requests
+                            .iter()
+                            .all(|request| is_idempotent(&request.method)) =>
                 {
-                    break;
+                    // lightningd closed the socket before answering (e.g. it
+                    // was restarted), the requests can be safely sent again.
+                    return self.resend(requests).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(responses)
+    }
+
+    /// Send the requests again on a new connection
+    async fn resend<S: Serialize>(&self, requests: &[Request<S>]) -> Result<Vec<Value>, Error> {
+        let conn = self.connection().await?;
+        let mut pending = Vec::with_capacity(requests.len());
+        for request in requests {
+            let id = request.id.as_ref().ok_or(Error::NoErrorOrResult)?;
+            pending.push(conn.send(id, request).await?);
+        }
+        let mut responses = Vec::with_capacity(pending.len());
+        for response in pending {
+            responses.push(response.wait().await?);
+        }
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{UnixListener, UnixStream};
+
+    use super::{Client, MessageBuffer};
+
+    #[tokio::test]
+    async fn responses_out_of_order() {
+        let path = std::env::temp_dir().join(format!("cln4rust-{}-async", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        // answer the two requests only once both are received, in reverse order
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = MessageBuffer::default();
+            let mut requests = Vec::new();
+            while requests.len() < 2 {
+                match buffer.next_message().unwrap() {
+                    Some(request) => requests.push(request),
+                    None => {
+                        let mut chunk = [0; 1024];
+                        let n = stream.read(&mut chunk).await.unwrap();
+                        buffer.extend(&chunk[..n]);
+                    }
                 }
             }
+            for request in requests.iter().rev() {
+                let response =
+                    json!({"jsonrpc": "2.0", "id": request["id"], "result": request["method"]});
+                stream
+                    .write_all(format!("{response}\n\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
 
-            // Deserialize the response
-            let response: Response<D> = Deserializer::from_slice(&response_data)
-                .into_iter()
-                .next()
-                .map_or(Err(Error::NoErrorOrResult), |res| Ok(res?))?;
-            if response
-                .jsonrpc
-                .as_ref()
-                .map_or(false, |version| version != "2.0")
-            {
-                return Err(Error::VersionMismatch);
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        let (first, second) = tokio::join!(
+            client.send_request::<Value, String>("waitanyinvoice", json!({})),
+            client.send_request::<Value, String>("getinfo", json!({})),
+        );
+        assert_eq!(first.unwrap().into_result().unwrap(), "waitanyinvoice");
+        assert_eq!(second.unwrap().into_result().unwrap(), "getinfo");
+    }
+
+    /// Read the next request sent on `stream`
+    async fn read_request(stream: &mut UnixStream, buffer: &mut MessageBuffer) -> Value {
+        loop {
+            if let Some(request) = buffer.next_message().unwrap() {
+                return request;
             }
+            let mut chunk = [0; 1024];
+            let n = stream.read(&mut chunk).await.unwrap();
+            buffer.extend(&chunk[..n]);
+        }
+    }
+
+    #[tokio::test]
+    async fn persistent_resends_after_restart() {
+        let path =
+            std::env::temp_dir().join(format!("cln4rust-{}-async-restart", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let restart_path = path.clone();
+        // answer the first request, then restart after receiving the second
+        // one, answering with the number of the listener
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = MessageBuffer::default();
+            let request = read_request(&mut stream, &mut buffer).await;
+            let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": 0});
+            stream
+                .write_all(format!("{response}\n\n").as_bytes())
+                .await
+                .unwrap();
+            read_request(&mut stream, &mut buffer).await;
+            drop(listener);
+            let _ = std::fs::remove_file(&restart_path);
+            let listener = UnixListener::bind(&restart_path).unwrap();
+            drop(stream);
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = MessageBuffer::default();
+            let request = read_request(&mut stream, &mut buffer).await;
+            let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": 1});
+            stream
+                .write_all(format!("{response}\n\n").as_bytes())
+                .await
+                .unwrap();
+        });
+
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        for expected in 0..2 {
+            let listener: u64 = client
+                .send_request("getinfo", json!({}))
+                .await
+                .and_then(|res| res.into_result())
+                .unwrap();
+            assert_eq!(listener, expected);
+        }
+    }
+
+    #[test]
+    fn decode_partial_message() {
+        let mut buffer = MessageBuffer::default();
+        buffer.extend(br#"{"id": 1}  {"id""#);
+        assert_eq!(buffer.next_message().unwrap(), Some(json!({"id": 1})));
+        assert_eq!(buffer.next_message().unwrap(), None);
+        buffer.extend(b": 2}\n\n");
+        assert_eq!(buffer.next_message().unwrap(), Some(json!({"id": 2})));
+        assert_eq!(buffer.next_message().unwrap(), None);
+        assert!(buffer.buffer.is_empty());
+    }
 
-            Ok(response)
+    #[test]
+    fn decode_message_read_byte_by_byte() {
+        let message = json!({
+            "id": "cln4rust/1",
+            "result": { "alias": "{[\"}\\", "channels": [[], [{}], "]"] }
+        });
+        let mut buffer = MessageBuffer::default();
+        let mut messages = Vec::new();
+        for byte in format!("{message}\n\n{message}").as_bytes() {
+            buffer.extend(&[*byte]);
+            messages.extend(buffer.next_message().unwrap());
         }
+        assert_eq!(messages, [message.clone(), message]);
+        assert_eq!(buffer.next_message().unwrap(), None);
     }
 }
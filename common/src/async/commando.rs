@@ -0,0 +1,193 @@
+//! Async Commando support
+//!
+//! Support for sending requests to a remote node through the `commando`
+//! command of a locally connected node asynchronously.
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::commando::{wrap_batch, CommandoRequest};
+use crate::errors::Error;
+use crate::r#async::Client;
+use crate::retry::is_idempotent;
+use crate::types::{BatchRequest, BatchResponse, Response};
+
+/// A handle to a remote node for async operations, reached through the
+/// `commando` command of a local node.
+///
+/// The requests are authorized by the remote node with the rune.
+#[derive(Debug)]
+pub struct Commando {
+    /// Client connected to the local node
+    client: Client,
+    /// Node id of the remote node, which must be connected to the local one
+    peer_id: String,
+    /// Rune authorizing the requests on the remote node
+    rune: String,
+}
+
+impl Commando {
+    /// Creates a new handle to the remote node `peer_id`, using the socket
+    /// of the local node.
+    pub fn new<P: AsRef<Path>>(sockpath: P, peer_id: &str, rune: &str) -> Commando {
+        Commando::with_client(Client::new(sockpath), peer_id, rune)
+    }
+
+    /// Creates a new handle to the remote node `peer_id`, using an
+    /// already configured client of the local node.
+    pub fn with_client(client: Client, peer_id: &str, rune: &str) -> Commando {
+        Commando {
+            client,
+            peer_id: peer_id.to_owned(),
+            rune: rune.to_owned(),
+        }
+    }
+
+    /// Get reference to the client of the local node
+    pub fn client(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    /// Sends a request to the remote node asynchronously
+    pub async fn send_request<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_request_with_filter(method, params, None).await
+    }
+
+    /// Sends a request to the remote node with a `filter` that trims the
+    /// response to the fields listed in it.
+    pub async fn send_request_with_filter<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        // the local client only sees `commando`, so the retries are decided
+        // here from the command run by the remote node
+        let policy = match self.client.retry_policy() {
+            Some(policy) if is_idempotent(method) => policy,
+            _ => return self.send_once(method, params, filter).await,
+        };
+        let mut attempt = 1;
+        loop {
+            let response = self.send_once(method, &params, filter.clone()).await;
+            if !policy.should_retry_response(attempt, &response) {
+                return response;
+            }
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request to the remote node only once, even if a retry
+    /// policy is set on the local client.
+    pub async fn send_request_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_once(method, params, None).await
+    }
+
+    pub(crate) async fn send_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let request = CommandoRequest {
+            peer_id: &self.peer_id,
+            method,
+            params,
+            rune: &self.rune,
+            filter,
+        };
+        self.client.send_once("commando", request, None).await
+    }
+
+    /// Sends all the calls of the batch to the remote node together.
+    pub async fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        self.client
+            .send_batch(&wrap_batch(&self.peer_id, &self.rune, batch))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    use super::Commando;
+    use crate::retry::RetryPolicy;
+
+    #[tokio::test]
+    async fn retry_read_only_commands_of_the_remote_node() {
+        let path = std::env::temp_dir().join(format!(
+            "cln4rust-{}-async-commando-retry",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let received = methods.clone();
+        // fail every other `commando` call with a transient error
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = Vec::new();
+                let request = loop {
+                    let mut chunk = [0; 1024];
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..n]);
+                    if let Some(Ok(request)) = serde_json::Deserializer::from_slice(&buffer)
+                        .into_iter::<Value>()
+                        .next()
+                    {
+                        break request;
+                    }
+                };
+                let method = request["params"]["method"].as_str().unwrap().to_owned();
+                let failed = {
+                    let mut received = received.lock().unwrap();
+                    received.push(method.clone());
+                    received.len() % 2 == 1
+                };
+                let response = if failed {
+                    json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -1, "message": "Still syncing with bitcoin network"}})
+                } else {
+                    json!({"jsonrpc": "2.0", "id": request["id"], "result": method})
+                };
+                stream
+                    .write_all(format!("{response}\n\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
+        let mut commando = Commando::new(&path, "02aa", "rune");
+        let mut policy = RetryPolicy::new(3);
+        policy.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+        commando.client().set_retry_policy(Some(policy));
+
+        let method: String = commando
+            .send_request("listfunds", json!({}))
+            .await
+            .and_then(|res| res.into_result())
+            .unwrap();
+        assert_eq!(method, "listfunds");
+        assert!(commando
+            .send_request::<_, String>("pay", json!({}))
+            .await
+            .and_then(|res| res.into_result())
+            .is_err());
+        assert_eq!(*methods.lock().unwrap(), ["listfunds", "listfunds", "pay"]);
+    }
+}
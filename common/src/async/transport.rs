@@ -0,0 +1,159 @@
+//! Async Transport support
+//!
+//! The async counterpart of the `transport` module, implemented by the
+//! async clients and by the test transports.
+use std::future::{self, Future};
+
+use serde_json::Value;
+
+use crate::errors::Error;
+use crate::r#async::{Client, Commando};
+use crate::transport::{Mock, Recorder, Replay};
+use crate::types::{BatchRequest, BatchResponse, Response};
+
+/// A way to send requests to lightningd and receive its responses asynchronously
+pub trait Transport: Send + Sync {
+    /// Sends a request, returning the response of lightningd.
+    ///
+    /// When `filter` is set, the response is trimmed to the fields listed
+    /// in it, see the `filter` module.
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send;
+
+    /// Sends a request that must not be repeated (e.g. `pay`), so it is
+    /// never retried by the transport.
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        self.send(method, params, filter)
+    }
+
+    /// Sends all the calls of the batch, and waits for all their responses.
+    ///
+    /// By default the calls are sent one after the other.
+    fn send_batch(
+        &self,
+        batch: &BatchRequest,
+    ) -> impl Future<Output = Result<BatchResponse, Error>> + Send {
+        async move {
+            let mut responses = Vec::with_capacity(batch.len());
+            for (method, params) in batch.calls() {
+                responses.push(self.send(method, params.clone(), None).await?);
+            }
+            Ok(BatchResponse::new(batch, responses))
+        }
+    }
+}
+
+impl Transport for Client {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        self.send_request_with_filter(method, params, filter)
+    }
+
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        Client::send_once(self, method, params, filter)
+    }
+
+    fn send_batch(
+        &self,
+        batch: &BatchRequest,
+    ) -> impl Future<Output = Result<BatchResponse, Error>> + Send {
+        Client::send_batch(self, batch)
+    }
+}
+
+impl Transport for Commando {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        self.send_request_with_filter(method, params, filter)
+    }
+
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        Commando::send_once(self, method, params, filter)
+    }
+
+    fn send_batch(
+        &self,
+        batch: &BatchRequest,
+    ) -> impl Future<Output = Result<BatchResponse, Error>> + Send {
+        Commando::send_batch(self, batch)
+    }
+}
+
+impl Transport for Mock {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        _filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        future::ready(Ok(self.respond(method, params)))
+    }
+}
+
+impl<T: Transport> Transport for Recorder<T> {
+    async fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        let response = self
+            .inner_ref()
+            .send(method, params.clone(), filter)
+            .await?;
+        self.record(method, params, &response);
+        Ok(response)
+    }
+
+    async fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        let response = self
+            .inner_ref()
+            .send_once(method, params.clone(), filter)
+            .await?;
+        self.record(method, params, &response);
+        Ok(response)
+    }
+}
+
+impl Transport for Replay {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        _filter: Option<Value>,
+    ) -> impl Future<Output = Result<Response<Value>, Error>> + Send {
+        future::ready(self.respond(method, params))
+    }
+}
@@ -15,35 +15,257 @@
 
 //! Client support
 //!
-//! Support for connecting to JSONRPC servers over UNIX socets, sending requests,
+//! Support for connecting to JSONRPC servers over UNIX or TCP sockets, sending requests,
 //! and parsing responses
 //!
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
 use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Duration;
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
-use serde_json::{to_writer, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Deserializer, Value};
 
 use crate::errors::Error;
-use crate::types::{Request, Response};
+use crate::retry::{is_idempotent, RetryPolicy};
+use crate::types::{BatchRequest, BatchResponse, Id, Notification, Request, Response};
 
 /// A handle to a remote JSONRPC server
 #[derive(Debug)]
 pub struct Client {
-    /// field that defines path to the lightning-rpc socket file
-    sockpath: PathBuf,
+    /// field that defines the address of the lightning-rpc socket
+    endpoint: Endpoint,
     /// timeout for RPC request
     timeout: Option<Duration>,
+    /// keep the socket open between requests instead of connecting for each call
+    persistent: bool,
+    /// connection kept open between requests when running in persistent mode
+    conn: Mutex<Option<Connection>>,
+    /// counter used to generate the id of the next request
+    next_id: AtomicU64,
+    /// callback receiving the notifications sent by lightningd, if enabled
+    notifications: Option<NotificationHandler>,
+    /// policy used to retry the requests failing for a transient reason
+    retry: Option<RetryPolicy>,
+}
+
+/// Address of the lightning-rpc socket
+#[derive(Debug, Clone)]
+pub(crate) enum Endpoint {
+    /// Path to the lightning-rpc socket file
+    Unix(PathBuf),
+    /// TCP address forwarding to the socket (e.g. with socat or ssh)
+    Tcp(String),
+}
+
+/// Stream connected to the lightning-rpc socket
+#[derive(Debug)]
+enum Stream {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Stream {
+    fn connect(endpoint: &Endpoint) -> io::Result<Stream> {
+        match endpoint {
+            Endpoint::Unix(sockpath) => UnixStream::connect(sockpath).map(Stream::Unix),
+            Endpoint::Tcp(addr) => TcpStream::connect(addr).map(Stream::Tcp),
+        }
+    }
+
+    fn try_clone(&self) -> io::Result<Stream> {
+        match self {
+            Stream::Unix(stream) => stream.try_clone().map(Stream::Unix),
+            Stream::Tcp(stream) => stream.try_clone().map(Stream::Tcp),
+        }
+    }
+
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Stream::Unix(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+            Stream::Tcp(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Unix(stream) => stream.read(buf),
+            Stream::Tcp(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Unix(stream) => stream.write(buf),
+            Stream::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Unix(stream) => stream.flush(),
+            Stream::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Callback receiving the notifications sent by lightningd
+#[derive(Clone)]
+struct NotificationHandler(Arc<dyn Fn(Notification) + Send + Sync>);
+
+impl fmt::Debug for NotificationHandler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("NotificationHandler")
+    }
+}
+
+/// An open connection to the lightning-rpc socket
+#[derive(Debug)]
+struct Connection {
+    reader: BufReader<Stream>,
+    writer: Stream,
+    notifications: Option<NotificationHandler>,
+}
+
+impl Connection {
+    fn open(
+        endpoint: &Endpoint,
+        timeout: Option<Duration>,
+        notifications: Option<NotificationHandler>,
+    ) -> Result<Connection, Error> {
+        let stream = Stream::connect(endpoint)?;
+        let writer = stream.try_clone()?;
+        let conn = Connection {
+            reader: BufReader::new(stream),
+            writer,
+            notifications,
+        };
+        conn.set_timeout(timeout)?;
+        Ok(conn)
+    }
+
+    fn set_timeout(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.writer.set_timeout(timeout)?;
+        Ok(())
+    }
+
+    /// Write all the requests at once on the socket
+    fn write_requests<S: Serialize>(&mut self, requests: &[Request<S>]) -> Result<(), Error> {
+        let mut data = Vec::new();
+        for request in requests {
+            serde_json::to_writer(&mut data, request)?;
+        }
+        self.writer.write_all(&data)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Wait for lightningd to answer, returning `false` if it closed the
+    /// connection before sending anything back.
+    ///
+    /// The blank lines left after the previous messages are skipped.
+    fn wait_answer(&mut self) -> Result<bool, Error> {
+        loop {
+            let blanks = match self.reader.fill_buf() {
+                Ok([]) => return Ok(false),
+                Ok(buf) => match buf.iter().position(|byte| !byte.is_ascii_whitespace()) {
+                    Some(_) => return Ok(true),
+                    None => buf.len(),
+                },
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset
+                    ) =>
+                {
+                    return Ok(false)
+                }
+                Err(err) => return Err(err.into()),
+            };
+            self.reader.consume(blanks);
+        }
+    }
+
+    /// Read the next JSON message sent by lightningd.
+    fn read_message(&mut self) -> Result<Value, Error> {
+        match Deserializer::from_reader(&mut self.reader)
+            .into_iter::<Value>()
+            .next()
+        {
+            Some(message) => Ok(message?),
+            None => Err(Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed by lightningd",
+            ))),
+        }
+    }
+
+    /// Read messages until all the responses to the requests `ids` arrived,
+    /// returning them in the same order as `ids`.
+    fn read_responses(&mut self, ids: &[Id]) -> Result<Vec<Value>, Error> {
+        let mut responses = vec![None; ids.len()];
+        let mut missing = ids.len();
+        while missing > 0 {
+            let message = self.read_message()?;
+            let Some(id) = message.get("id") else {
+                // a notification about one of the running commands
+                if let Some(NotificationHandler(handler)) = &self.notifications {
+                    if let Ok(notification) = Notification::deserialize(&message) {
+                        handler(notification);
+                    }
+                }
+                continue;
+            };
+            let id = Id::deserialize(id).ok();
+            let index = ids
+                .iter()
+                .position(|request_id| Some(request_id) == id.as_ref())
+                .ok_or(Error::NonceMismatch)?;
+            if responses[index].replace(message).is_none() {
+                missing -= 1;
+            }
+        }
+        Ok(responses.into_iter().flatten().collect())
+    }
 }
 
 impl Client {
     /// Creates a new client using the path to the socket file and initializing the timeout field to None
     pub fn new<P: AsRef<Path>>(sockpath: P) -> Client {
+        Client::with_endpoint(Endpoint::Unix(sockpath.as_ref().to_path_buf()))
+    }
+
+    /// Creates a new client connecting over TCP to `addr` (e.g. `127.0.0.1:9735`),
+    /// where the lightning-rpc socket is forwarded with tools like socat or ssh.
+    pub fn new_tcp(addr: &str) -> Client {
+        Client::with_endpoint(Endpoint::Tcp(addr.to_owned()))
+    }
+
+    fn with_endpoint(endpoint: Endpoint) -> Client {
         Client {
-            sockpath: sockpath.as_ref().to_path_buf(),
+            endpoint,
             timeout: None,
+            persistent: false,
+            conn: Mutex::new(None),
+            next_id: AtomicU64::new(0),
+            notifications: None,
+            retry: None,
         }
     }
 
@@ -52,40 +274,441 @@ impl Client {
         self.timeout = timeout;
     }
 
+    /// Set an optional policy to retry the requests failing for a transient
+    /// reason, see the `retry` module.
+    ///
+    /// The commands that can not be safely repeated (e.g. `pay`) and the
+    /// batches are never retried.
+    pub fn set_retry_policy(&mut self, retry: Option<RetryPolicy>) {
+        self.retry = retry;
+    }
+
+    /// The policy retrying the requests, if any
+    pub(crate) fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    /// Keep a single connection open and reuse it for all the requests.
+    ///
+    /// When lightningd restarts, the connection is transparently reopened
+    /// on the next request. A request left unanswered by the restart is sent
+    /// again only if it can be safely repeated (e.g. `getinfo`, not `pay`).
+    pub fn set_persistent(&mut self, persistent: bool) {
+        self.persistent = persistent;
+        if !persistent {
+            self.conn
+                .get_mut()
+                .unwrap_or_else(|err| err.into_inner())
+                .take();
+        }
+    }
+
+    /// Enable the notifications sent by lightningd while a command is
+    /// running (e.g. the progress of `pay`), calling `handler` for each of them.
+    ///
+    /// Notifications are enabled on every new connection, so this works
+    /// best together with `set_persistent`.
+    pub fn set_notification_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(Notification) + Send + Sync + 'static,
+    {
+        self.notifications = Some(NotificationHandler(Arc::new(handler)));
+        self.conn
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner())
+            .take();
+    }
+
+    /// Open a new connection, enabling the notifications if requested
+    fn connect(&self) -> Result<Connection, Error> {
+        let mut conn = Connection::open(&self.endpoint, self.timeout, self.notifications.clone())?;
+        if self.notifications.is_some() {
+            let id = self.next_id();
+            let request = Request {
+                method: "notifications".to_owned(),
+                params: json!({ "enable": true }),
+                id: Some(id.clone()),
+                jsonrpc: "2.0".to_owned(),
+                filter: None,
+            };
+            conn.write_requests(&[request])?;
+            let message = conn.read_responses(&[id])?.remove(0);
+            Response::<Value>::from_message(message)?.into_result()?;
+        }
+        Ok(conn)
+    }
+
+    /// Generate a new unique id for a request
+    fn next_id(&self) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        Id::Str(format!("cln4rust/{id}"))
+    }
+
     /// Sends a request to a client
     pub fn send_request<S: Serialize, D: DeserializeOwned>(
         &self,
         method: &str,
         params: S,
     ) -> Result<Response<D>, Error> {
-        // Setup connection
-        let mut stream = UnixStream::connect(&self.sockpath)?;
-        stream.set_read_timeout(self.timeout)?;
-        stream.set_write_timeout(self.timeout)?;
-
-        to_writer(
-            &mut stream,
-            &Request {
+        self.send_request_with_filter(method, params, None)
+    }
+
+    /// Sends a request with a `filter` that trims the response to the
+    /// fields listed in it, see the `filter` module.
+    pub fn send_request_with_filter<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let policy = match &self.retry {
+            Some(policy) if is_idempotent(method) => policy,
+            _ => return self.send_once(method, &params, filter),
+        };
+        let mut attempt = 1;
+        loop {
+            let response = self.send_once(method, &params, filter.clone());
+            if !policy.should_retry_response(attempt, &response) {
+                return response;
+            }
+            thread::sleep(policy.backoff(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request only once, even if a retry policy is set, for the
+    /// commands that must not be repeated.
+    pub fn send_request_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_once(method, params, None)
+    }
+
+    pub(crate) fn send_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let request = Request {
+            method: method.to_owned(),
+            params,
+            id: Some(self.next_id()),
+            jsonrpc: "2.0".to_owned(),
+            filter,
+        };
+        let message = self.exchange(&[request])?.remove(0);
+        Response::from_message(message)
+    }
+
+    /// Sends all the calls of the batch together, and waits for all their responses.
+    ///
+    /// The error returned by lightningd for one of the calls is reported in its
+    /// own response, the other calls of the batch are not affected.
+    pub fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        let requests: Vec<_> = batch
+            .calls()
+            .iter()
+            .map(|(method, params)| Request {
                 method: method.to_owned(),
                 params,
-                // FIXME: use a custom json to identify the json request!
-                id: Some("0".into()), // we always open a new connection, so we don't have to care about the nonce
+                id: Some(self.next_id()),
                 jsonrpc: "2.0".to_owned(),
-            },
-        )?;
-
-        let response: Response<D> = Deserializer::from_reader(&mut stream)
+                filter: None,
+            })
+            .collect();
+        let responses = self
+            .exchange(&requests)?
             .into_iter()
-            .next()
-            .map_or(Err(Error::NoErrorOrResult), |res| Ok(res?))?;
-        if response
-            .jsonrpc
-            .as_ref()
-            .map_or(false, |version| version != "2.0")
-        {
-            return Err(Error::VersionMismatch);
+            .map(Response::from_message)
+            .collect::<Result<_, _>>()?;
+        Ok(BatchResponse::new(batch, responses))
+    }
+
+    /// Write the requests and wait for their responses
+    fn exchange<S: Serialize>(&self, requests: &[Request<S>]) -> Result<Vec<Value>, Error> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids: Vec<Id> = requests
+            .iter()
+            .filter_map(|request| request.id.clone())
+            .collect();
+
+        if !self.persistent {
+            let mut conn = self.connect()?;
+            conn.write_requests(requests)?;
+            return conn.read_responses(&ids);
+        }
+
+        let mut guard = self.conn.lock().unwrap_or_else(|err| err.into_inner());
+        // the connection is put back only if the exchange succeeds, so a
+        // connection left in an unknown state is never reused.
+        let mut conn = match guard.take() {
+            Some(mut conn) => {
+                conn.set_timeout(self.timeout)?;
+                match conn.write_requests(requests) {
+                    Ok(()) => {
+                        // the write may also succeed on a socket closed by
+                        // lightningd, the requests are then sent again if
+                        // nothing was answered and they can be safely repeated.
+                        let idempotent = requests
+                            .iter()
+                            .all(|request| is_idempotent(&request.method));
+                        if idempotent && !conn.wait_answer()? {
+                            conn = self.connect()?;
+                            conn.write_requests(requests)?;
+                        }
+                        conn
+                    }
+                    Err(_) => {
+                        // lightningd closed the socket (e.g. it was restarted),
+                        // so the requests never reached it and we can try again.
+                        let mut conn = self.connect()?;
+                        conn.write_requests(requests)?;
+                        conn
+                    }
+                }
+            }
+            None => {
+                let mut conn = self.connect()?;
+                conn.write_requests(requests)?;
+                conn
+            }
+        };
+        let responses = conn.read_responses(&ids)?;
+        *guard = Some(conn);
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Write};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use super::Client;
+    use crate::errors::Error;
+    use crate::retry::RetryPolicy;
+    use crate::types::{BatchRequest, Notification};
+
+    /// Serve `conns` connections, answering `per_conn` requests on each
+    /// with the number of the connection that received them. A message is
+    /// sent on the returned channel every time a connection is closed.
+    fn fake_lightningd(name: &str, conns: usize, per_conn: usize) -> (PathBuf, mpsc::Receiver<()>) {
+        let path = std::env::temp_dir().join(format!("cln4rust-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let (closed, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for conn in 0..conns {
+                let (stream, _) = listener.accept().unwrap();
+                let mut writer = stream.try_clone().unwrap();
+                let mut reader = serde_json::Deserializer::from_reader(BufReader::new(stream))
+                    .into_iter::<Value>();
+                for _ in 0..per_conn {
+                    let request = reader.next().unwrap().unwrap();
+                    if request["method"] == "pay" {
+                        let progress = json!({"jsonrpc": "2.0", "method": "progress", "params": {"id": request["id"], "num": 1, "total": 2}});
+                        writer
+                            .write_all(format!("{progress}\n\n").as_bytes())
+                            .unwrap();
+                    }
+                    let response = if request["method"] == "fail" {
+                        json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -1, "message": "failed"}})
+                    } else if request["method"] == "wait" {
+                        json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": 2000, "message": "Timed out"}})
+                    } else if request["method"] == "listpeers" && conn == 0 {
+                        json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -1, "message": "Still syncing with bitcoin network"}})
+                    } else {
+                        json!({"jsonrpc": "2.0", "id": request["id"], "result": conn})
+                    };
+                    writer
+                        .write_all(format!("{response}\n\n").as_bytes())
+                        .unwrap();
+                }
+                drop(reader);
+                drop(writer);
+                let _ = closed.send(());
+            }
+        });
+        (path, rx)
+    }
+
+    #[test]
+    fn persistent_reuses_connection() {
+        let (path, _) = fake_lightningd("reuse", 1, 3);
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        for _ in 0..3 {
+            let conn: u64 = client
+                .send_request("getinfo", json!({}))
+                .and_then(|res| res.into_result())
+                .unwrap();
+            assert_eq!(conn, 0);
         }
+    }
+
+    #[test]
+    fn persistent_reconnects() {
+        let (path, closed) = fake_lightningd("reconnect", 2, 1);
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        for expected in 0..2 {
+            if expected > 0 {
+                closed.recv().unwrap();
+            }
+            let conn: u64 = client
+                .send_request("getinfo", json!({}))
+                .and_then(|res| res.into_result())
+                .unwrap();
+            assert_eq!(conn, expected);
+        }
+    }
+
+    /// Answer the first request, then drop the connection on the next one
+    /// and restart the listener, answering `getinfo` with the number of the
+    /// listener on every connection.
+    fn restarting_lightningd(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("cln4rust-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let restart_path = path.clone();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader =
+                serde_json::Deserializer::from_reader(BufReader::new(stream)).into_iter::<Value>();
+            let request = reader.next().unwrap().unwrap();
+            let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": 0});
+            writer
+                .write_all(format!("{response}\n\n").as_bytes())
+                .unwrap();
+            // lightningd restarts after receiving the second request, the new
+            // listener is ready before the old connection is closed
+            reader.next().unwrap().unwrap();
+            drop(listener);
+            let _ = std::fs::remove_file(&restart_path);
+            let listener = UnixListener::bind(&restart_path).unwrap();
+            drop(reader);
+            drop(writer);
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                let mut writer = stream.try_clone().unwrap();
+                let reader = serde_json::Deserializer::from_reader(BufReader::new(stream))
+                    .into_iter::<Value>();
+                for request in reader {
+                    let request = request.unwrap();
+                    let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": 1});
+                    writer
+                        .write_all(format!("{response}\n\n").as_bytes())
+                        .unwrap();
+                }
+            }
+        });
+        path
+    }
+
+    #[test]
+    fn persistent_resends_after_restart() {
+        let path = restarting_lightningd("restart");
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        for expected in 0..2 {
+            let listener: u64 = client
+                .send_request("getinfo", json!({}))
+                .and_then(|res| res.into_result())
+                .unwrap();
+            assert_eq!(listener, expected);
+        }
+    }
+
+    #[test]
+    fn persistent_does_not_resend_pay_after_restart() {
+        let path = restarting_lightningd("restart-pay");
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        let listener: u64 = client
+            .send_request("getinfo", json!({}))
+            .and_then(|res| res.into_result())
+            .unwrap();
+        assert_eq!(listener, 0);
+        assert!(matches!(
+            client.send_request::<_, u64>("pay", json!({})),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn retry_transient_errors() {
+        let (path, _) = fake_lightningd("retry", 2, 1);
+        let mut client = Client::new(&path);
+        let mut policy = RetryPolicy::new(3);
+        policy.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+        client.set_retry_policy(Some(policy));
+        let conn: u64 = client
+            .send_request("listpeers", json!({}))
+            .and_then(|res| res.into_result())
+            .unwrap();
+        assert_eq!(conn, 1);
+    }
+
+    #[test]
+    fn wait_timeout_is_not_retried() {
+        // a single connection: a second attempt could not connect
+        let (path, _) = fake_lightningd("wait-timeout", 1, 1);
+        let mut client = Client::new(&path);
+        let mut policy = RetryPolicy::new(3);
+        policy.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+        client.set_retry_policy(Some(policy));
+        let response = client
+            .send_request::<_, Value>(
+                "wait",
+                json!({"subsystem": "invoices", "indexname": "created", "nextvalue": 1}),
+            )
+            .and_then(|res| res.into_result());
+        assert!(matches!(response, Err(Error::Rpc(err)) if err.code == 2000));
+    }
+
+    #[test]
+    fn batch_keeps_errors_separate() {
+        let (path, _) = fake_lightningd("batch", 1, 3);
+        let client = Client::new(&path);
+        let mut batch = BatchRequest::new();
+        let first = batch.add::<_, u64>("getinfo", json!({})).unwrap();
+        let failed = batch.add::<_, u64>("fail", json!({})).unwrap();
+        let last = batch.add::<_, u64>("listfunds", json!({})).unwrap();
+        let mut response = client.send_batch(&batch).unwrap();
+        assert_eq!(response.take(last).unwrap(), 0);
+        assert!(matches!(response.take(failed), Err(Error::Rpc(err)) if err.code == -1));
+        assert_eq!(response.take(first).unwrap(), 0);
+    }
 
-        Ok(response)
+    #[test]
+    fn notifications_are_dispatched() {
+        let (path, _) = fake_lightningd("notifications", 1, 2);
+        let mut client = Client::new(&path);
+        client.set_persistent(true);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        client
+            .set_notification_handler(move |notification| sink.lock().unwrap().push(notification));
+        let conn: u64 = client
+            .send_request("pay", json!({}))
+            .and_then(|res| res.into_result())
+            .unwrap();
+        assert_eq!(conn, 0);
+        let received = received.lock().unwrap();
+        assert!(
+            matches!(&received[..], [Notification::Progress(progress)] if progress.num == 1 && progress.total == 2)
+        );
     }
 }
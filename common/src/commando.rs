@@ -0,0 +1,238 @@
+//! Commando support
+//!
+//! Support for sending requests to a remote node through the `commando`
+//! command of a locally connected node, authorizing them with a rune.
+use std::path::Path;
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::client::Client;
+use crate::errors::Error;
+use crate::retry::is_idempotent;
+use crate::types::{BatchRequest, BatchResponse, Response};
+
+/// Params of the `commando` command wrapping a request for the remote node
+#[derive(Debug, Serialize)]
+pub(crate) struct CommandoRequest<'a, S> {
+    pub(crate) peer_id: &'a str,
+    pub(crate) method: &'a str,
+    pub(crate) params: S,
+    pub(crate) rune: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<Value>,
+}
+
+/// Wrap all the calls of the batch into `commando` calls
+pub(crate) fn wrap_batch(peer_id: &str, rune: &str, batch: &BatchRequest) -> BatchRequest {
+    // keep the id of the batch, so that its entries can take the results
+    let mut wrapped = BatchRequest {
+        id: batch.id,
+        calls: Vec::with_capacity(batch.len()),
+    };
+    for (method, params) in batch.calls() {
+        let request = CommandoRequest {
+            peer_id,
+            method,
+            params,
+            rune,
+            filter: None,
+        };
+        // the params are already valid JSON, so this can not fail
+        let _ = wrapped.add::<_, Value>("commando", request);
+    }
+    wrapped
+}
+
+/// A handle to a remote node, reached through the `commando` command of
+/// a local node.
+///
+/// The requests are authorized by the remote node with the rune.
+#[derive(Debug)]
+pub struct Commando {
+    /// client connected to the local node
+    client: Client,
+    /// node id of the remote node, which must be connected to the local one
+    peer_id: String,
+    /// rune authorizing the requests on the remote node
+    rune: String,
+}
+
+impl Commando {
+    /// Creates a new handle to the remote node `peer_id`, using the socket
+    /// of the local node.
+    pub fn new<P: AsRef<Path>>(sockpath: P, peer_id: &str, rune: &str) -> Commando {
+        Commando::with_client(Client::new(sockpath), peer_id, rune)
+    }
+
+    /// Creates a new handle to the remote node `peer_id`, using an
+    /// already configured client of the local node.
+    pub fn with_client(client: Client, peer_id: &str, rune: &str) -> Commando {
+        Commando {
+            client,
+            peer_id: peer_id.to_owned(),
+            rune: rune.to_owned(),
+        }
+    }
+
+    /// Get reference to the client of the local node
+    pub fn client(&mut self) -> &mut Client {
+        &mut self.client
+    }
+
+    /// Sends a request to the remote node
+    pub fn send_request<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_request_with_filter(method, params, None)
+    }
+
+    /// Sends a request to the remote node with a `filter` that trims the
+    /// response to the fields listed in it.
+    pub fn send_request_with_filter<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        // the local client only sees `commando`, so the retries are decided
+        // here from the command run by the remote node
+        let policy = match self.client.retry_policy() {
+            Some(policy) if is_idempotent(method) => policy,
+            _ => return self.send_once(method, params, filter),
+        };
+        let mut attempt = 1;
+        loop {
+            let response = self.send_once(method, &params, filter.clone());
+            if !policy.should_retry_response(attempt, &response) {
+                return response;
+            }
+            thread::sleep(policy.backoff(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request to the remote node only once, even if a retry
+    /// policy is set on the local client.
+    pub fn send_request_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+    ) -> Result<Response<D>, Error> {
+        self.send_once(method, params, None)
+    }
+
+    pub(crate) fn send_once<S: Serialize, D: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: S,
+        filter: Option<Value>,
+    ) -> Result<Response<D>, Error> {
+        let request = CommandoRequest {
+            peer_id: &self.peer_id,
+            method,
+            params,
+            rune: &self.rune,
+            filter,
+        };
+        self.client.send_once("commando", request, None)
+    }
+
+    /// Sends all the calls of the batch to the remote node together.
+    pub fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        self.client
+            .send_batch(&wrap_batch(&self.peer_id, &self.rune, batch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use super::{Commando, CommandoRequest};
+    use crate::retry::RetryPolicy;
+
+    /// Local node answering the `commando` calls, failing every other one with
+    /// a transient error and answering the others with the method run by the
+    /// remote node. Returns the socket path and the methods received.
+    fn fake_lightningd(name: &str) -> (PathBuf, Arc<Mutex<Vec<String>>>) {
+        let path = std::env::temp_dir().join(format!("cln4rust-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let received = methods.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request: Value = serde_json::Deserializer::from_reader(&stream)
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .unwrap();
+                let method = request["params"]["method"].as_str().unwrap().to_owned();
+                let mut received = received.lock().unwrap();
+                received.push(method.clone());
+                let response = if received.len() % 2 == 1 {
+                    json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -1, "message": "Still syncing with bitcoin network"}})
+                } else {
+                    json!({"jsonrpc": "2.0", "id": request["id"], "result": method})
+                };
+                stream
+                    .write_all(format!("{response}\n\n").as_bytes())
+                    .unwrap();
+            }
+        });
+        (path, methods)
+    }
+
+    #[test]
+    fn retry_read_only_commands_of_the_remote_node() {
+        let (path, methods) = fake_lightningd("commando-retry");
+        let mut commando = Commando::new(&path, "02aa", "rune");
+        let mut policy = RetryPolicy::new(3);
+        policy.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+        commando.client().set_retry_policy(Some(policy));
+
+        let method: String = commando
+            .send_request("listfunds", json!({}))
+            .and_then(|res| res.into_result())
+            .unwrap();
+        assert_eq!(method, "listfunds");
+        assert!(commando
+            .send_request::<_, String>("pay", json!({}))
+            .and_then(|res| res.into_result())
+            .is_err());
+        assert_eq!(*methods.lock().unwrap(), ["listfunds", "listfunds", "pay"]);
+    }
+
+    #[test]
+    fn commando_request_layout() {
+        let request = CommandoRequest {
+            peer_id: "02aa",
+            method: "listfunds",
+            params: json!({"spent": true}),
+            rune: "rune",
+            filter: None,
+        };
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            json!({
+                "peer_id": "02aa",
+                "method": "listfunds",
+                "params": {"spent": true},
+                "rune": "rune",
+            })
+        );
+    }
+}
@@ -35,6 +35,11 @@ pub enum Error {
     NonceMismatch,
     /// Response to a request had a jsonrpc field other than "2.0"
     VersionMismatch,
+    /// Result of a batch entry taken from the response to another batch
+    BatchMismatch,
+    /// The PSBT exchange of a channel opening or splice did not secure the
+    /// commitments within the allowed number of rounds
+    CommitmentsNotSecured,
 }
 
 impl From<serde_json::Error> for Error {
@@ -64,6 +69,12 @@ impl fmt::Display for Error {
             Error::NoErrorOrResult => write!(f, "Malformed RPC response"),
             Error::NonceMismatch => write!(f, "Nonce of response did not match nonce of request"),
             Error::VersionMismatch => write!(f, "`jsonrpc` field set to non-\"2.0\""),
+            Error::BatchMismatch => {
+                write!(f, "Batch entry taken from the response to another batch")
+            }
+            Error::CommitmentsNotSecured => {
+                write!(f, "Commitments not secured after the allowed PSBT updates")
+            }
         }
     }
 }
@@ -88,3 +99,560 @@ pub struct RpcError {
     /// Additional data specific to the error
     pub data: Option<serde_json::Value>,
 }
+
+/// Define the `RpcErrorCode` enum together with the mapping to its numeric code.
+macro_rules! rpc_error_codes {
+    ($($(#[$doc:meta])* $name:ident = $code:expr,)*) => {
+        /// Error codes returned by lightningd, as documented in
+        /// `common/jsonrpc_errors.h` of Core Lightning.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum RpcErrorCode {
+            $($(#[$doc])* $name,)*
+            /// A code not known by this library
+            Unknown(i32),
+        }
+
+        impl RpcErrorCode {
+            /// The numeric code sent by lightningd
+            pub fn code(self) -> i32 {
+                match self {
+                    $(RpcErrorCode::$name => $code,)*
+                    RpcErrorCode::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl From<i32> for RpcErrorCode {
+            fn from(code: i32) -> Self {
+                match code {
+                    $($code => RpcErrorCode::$name,)*
+                    code => RpcErrorCode::Unknown(code),
+                }
+            }
+        }
+    };
+}
+
+rpc_error_codes! {
+    // Standard errors defined by JSONRPC 2.0
+    /// The JSON sent is not a valid request object
+    InvalidRequest = -32600,
+    /// The method does not exist
+    MethodNotFound = -32601,
+    /// Invalid method parameters
+    InvalidParams = -32602,
+
+    // Uncategorized errors
+    /// Generic lightningd error
+    Lightningd = -1,
+    /// A developer option was used without `--developer`
+    ParamDevError = -2,
+    /// The plugin serving the command returned an error
+    PluginError = -3,
+    /// The plugin serving the command terminated
+    PluginTerminated = -4,
+    /// lightningd is shutting down
+    LightningdShutdown = -5,
+
+    // Errors from `pay`, `sendpay`, or `waitsendpay` commands
+    /// A payment with the same payment hash is still in progress
+    PayInProgress = 200,
+    /// The payment hash was already paid with a different amount or destination
+    PayRhashAlreadyUsed = 201,
+    /// The onion reply could not be parsed
+    PayUnparseableOnion = 202,
+    /// The destination reported a permanent failure
+    PayDestinationPermFail = 203,
+    /// A node along the route failed, another route may succeed
+    PayTryOtherRoute = 204,
+    /// No route to the destination was found
+    PayRouteNotFound = 205,
+    /// The route found exceeds the fee limits
+    PayRouteTooExpensive = 206,
+    /// The invoice expired
+    PayInvoiceExpired = 207,
+    /// No payment with the given payment hash
+    PayNoSuchPayment = 208,
+    /// Unspecified payment error
+    PayUnspecifiedError = 209,
+    /// The payment failed after retrying for too long
+    PayStoppedRetrying = 210,
+    /// The payment status is not the expected one
+    PayStatusUnexpected = 211,
+    /// The invoice request is invalid
+    PayInvoiceRequestInvalid = 212,
+    /// The invoice was not preapproved by the HSM
+    PayInvoicePreapprovalDeclined = 213,
+    /// The keysend was not preapproved by the HSM
+    PayKeysendPreapprovalDeclined = 214,
+    /// The onion given to `injectpaymentonion` failed
+    PayInjectPaymentOnionFailed = 218,
+
+    // `fundchannel` or `withdraw` errors
+    /// The amount exceeds the maximum channel size
+    FundMaxExceeded = 300,
+    /// The wallet does not have enough funds
+    FundCannotAfford = 301,
+    /// The output would be dust
+    FundOutputIsDust = 302,
+    /// The funding transaction could not be broadcast
+    FundingBroadcastFail = 303,
+    /// lightningd is still syncing with bitcoind
+    FundingStillSyncingBitcoin = 304,
+    /// The peer is not connected
+    FundingPeerNotConnected = 305,
+    /// The peer is unknown
+    FundingUnknownPeer = 306,
+    /// There is no channel opening to cancel
+    FundingNothingToCancel = 307,
+    /// It is not safe to cancel the channel opening anymore
+    FundingCancelNotSafe = 308,
+    /// The PSBT is not valid
+    FundingPsbtInvalid = 309,
+    /// The peer does not support dual funding
+    FundingV2NotSupported = 310,
+    /// The channel is unknown
+    FundingUnknownChannel = 311,
+    /// The channel is not in the expected state
+    FundingStateInvalid = 312,
+    /// The funds would not be enough for the emergency reserve
+    FundCannotAffordWithEmergency = 313,
+
+    // Splice errors
+    /// The splice transaction could not be broadcast
+    SpliceBroadcastFail = 350,
+    /// The splice was started by the other side
+    SpliceWrongOwner = 351,
+    /// The channel to splice is unknown
+    SpliceUnknownChannel = 352,
+    /// The channel is not in a state that allows splicing
+    SpliceInvalidChannelState = 353,
+    /// The peer does not support splicing
+    SpliceNotSupported = 354,
+    /// A splice is already in progress
+    SpliceBusyError = 355,
+    /// The splice inputs are not valid
+    SpliceInputError = 356,
+    /// The splice does not have enough funding
+    SpliceFundingLow = 357,
+    /// The splice fee is too low
+    SpliceLowFee = 358,
+    /// The splice fee is too high
+    SpliceHighFee = 359,
+
+    // `connect` errors
+    /// No address is known for the peer
+    ConnectNoKnownAddress = 400,
+    /// All the addresses of the peer failed
+    ConnectAllAddressesFailed = 401,
+    /// The peer disconnected while connecting
+    ConnectDisconnectedDuring = 402,
+
+    // Errors from the `bcli` plugin
+    /// bitcoind returned an error
+    BcliError = 500,
+    /// bitcoind could not estimate the fees
+    BcliNoFeeEstimates = 501,
+
+    // HSM errors
+    /// The HSM failed to perform ECDH
+    HsmEcdhFailed = 800,
+
+    // Errors from `invoice` or `delinvoice` commands
+    /// An invoice with the same label already exists
+    InvoiceLabelAlreadyExists = 900,
+    /// An invoice with the same preimage already exists
+    InvoicePreimageAlreadyExists = 901,
+    /// The route hints gave no usable routes
+    InvoiceHintsGaveNoRoutes = 902,
+    /// The invoice expired while waiting for it
+    InvoiceExpiredDuringWait = 903,
+    /// Timed out waiting for the invoice
+    InvoiceWaitTimedOut = 904,
+    /// The invoice was not found
+    InvoiceNotFound = 905,
+    /// The invoice status is not the expected one
+    InvoiceStatusUnexpected = 906,
+    /// The offer of the invoice is not active
+    InvoiceOfferInactive = 907,
+    /// The invoice has no description
+    InvoiceNoDescription = 908,
+
+    // Errors from offers
+    /// An offer with the same id already exists
+    OfferAlreadyExists = 1000,
+    /// The offer is already disabled
+    OfferAlreadyDisabled = 1001,
+    /// The offer expired
+    OfferExpired = 1002,
+    /// No route to the offer issuer was found
+    OfferRouteNotFound = 1003,
+    /// The reply to the invoice request is not valid
+    OfferBadInvreqReply = 1004,
+    /// Timed out waiting for the reply
+    OfferTimeout = 1005,
+
+    // Errors from datastore and deldatastore
+    /// The key to delete does not exist
+    DatastoreDelDoesNotExist = 1200,
+    /// The generation of the key to delete does not match
+    DatastoreDelWrongGeneration = 1201,
+    /// The key to create already exists
+    DatastoreUpdateAlreadyExists = 1202,
+    /// The key to update does not exist
+    DatastoreUpdateDoesNotExist = 1203,
+    /// The generation of the key to update does not match
+    DatastoreUpdateWrongGeneration = 1204,
+    /// The key to update has children
+    DatastoreUpdateHasChildren = 1205,
+    /// The key to update is a parent of other keys
+    DatastoreUpdateNoChildren = 1206,
+
+    // Errors from `signmessage` and `checkmessage`
+    /// The public key of the signature was not found in the gossip
+    SignmessagePubkeyNotFound = 1301,
+
+    // Errors from `delforward`
+    /// The forward to delete was not found
+    DelforwardNotFound = 1401,
+
+    // Errors from runes
+    /// The rune is not valid for this node
+    RuneNotAuthorized = 1501,
+    /// The rune restrictions do not permit the command
+    RuneNotPermitted = 1502,
+    /// The rune is blacklisted
+    RuneBlacklisted = 1503,
+
+    // Errors from `wait`
+    /// Timed out waiting for the event
+    WaitTimeout = 2000,
+
+    // Errors from `commando`
+    /// The remote node returned an error
+    CommandoErrorRemote = 0x4c4f,
+    /// The rune was not accepted by the remote node
+    CommandoErrorRemoteAuth = 0x4c50,
+}
+
+impl RpcErrorCode {
+    /// Returns whether the caller may try again, e.g. pay along another route,
+    /// connect again or wait longer, possibly with other parameters.
+    ///
+    /// Those errors come from commands with side effects or with a deliberate
+    /// timeout, so they are never repeated by a `RetryPolicy`, which only
+    /// repeats the `is_transient` errors.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            RpcErrorCode::PayTryOtherRoute
+                | RpcErrorCode::PayRouteNotFound
+                | RpcErrorCode::PayStoppedRetrying
+                | RpcErrorCode::FundingStillSyncingBitcoin
+                | RpcErrorCode::FundingPeerNotConnected
+                | RpcErrorCode::SpliceBusyError
+                | RpcErrorCode::ConnectAllAddressesFailed
+                | RpcErrorCode::ConnectDisconnectedDuring
+                | RpcErrorCode::OfferRouteNotFound
+                | RpcErrorCode::OfferTimeout
+                | RpcErrorCode::InvoiceWaitTimedOut
+                | RpcErrorCode::WaitTimeout
+        )
+    }
+
+    /// Returns whether lightningd could not handle the call yet, so the very
+    /// same call may succeed if sent again later
+    pub fn is_transient(self) -> bool {
+        self == RpcErrorCode::FundingStillSyncingBitcoin
+    }
+
+    /// Returns whether the error comes from `pay`, `sendpay` or `waitsendpay`
+    pub fn is_pay_error(self) -> bool {
+        (200..300).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from `fundchannel` or `withdraw`
+    pub fn is_fund_error(self) -> bool {
+        (300..350).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from a splice command
+    pub fn is_splice_error(self) -> bool {
+        (350..400).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from `connect`
+    pub fn is_connect_error(self) -> bool {
+        (400..500).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from the invoice commands
+    pub fn is_invoice_error(self) -> bool {
+        (900..1000).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from the offer commands
+    pub fn is_offer_error(self) -> bool {
+        (1000..1100).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from the datastore commands
+    pub fn is_datastore_error(self) -> bool {
+        (1200..1300).contains(&self.code())
+    }
+
+    /// Returns whether the error comes from the rune checks
+    pub fn is_rune_error(self) -> bool {
+        (1500..1600).contains(&self.code())
+    }
+}
+
+impl From<&RpcError> for RpcErrorCode {
+    fn from(err: &RpcError) -> Self {
+        RpcErrorCode::from(err.code)
+    }
+}
+
+impl fmt::Display for RpcErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RpcErrorCode::Unknown(code) => write!(f, "unknown error code {code}"),
+            code => write!(f, "{code:?} ({})", code.code()),
+        }
+    }
+}
+
+/// `data` of the errors returned by `pay`, `sendpay` and `waitsendpay`.
+///
+/// Which fields are present depends on the error code.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PayFailure {
+    pub id: Option<u64>,
+    pub partid: Option<u64>,
+    pub payment_hash: Option<String>,
+    pub destination: Option<String>,
+    pub status: Option<String>,
+    pub amount_msat: Option<u64>,
+    pub amount_sent_msat: Option<u64>,
+    pub created_at: Option<u64>,
+    pub erring_index: Option<u64>,
+    pub failcode: Option<u32>,
+    pub failcodename: Option<String>,
+    pub erring_node: Option<String>,
+    pub erring_channel: Option<String>,
+    pub erring_direction: Option<u32>,
+    pub raw_message: Option<String>,
+    /// The fee of the route, for `PayRouteTooExpensive`
+    pub fee_msat: Option<u64>,
+    /// The fee percentage of the route, for `PayRouteTooExpensive`
+    pub feepercent: Option<f64>,
+    /// The fee limit, for `PayRouteTooExpensive`
+    pub maxfeepercent: Option<f64>,
+    /// The current time, for `PayInvoiceExpired`
+    pub now: Option<u64>,
+    /// The expiry of the invoice, for `PayInvoiceExpired`
+    pub expiry: Option<u64>,
+}
+
+/// Typed `data` of an `RpcError`
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcErrorData {
+    /// Details of a failed payment
+    PayFailure(Box<PayFailure>),
+    /// Data of an error without a known layout
+    Other(serde_json::Value),
+}
+
+impl RpcError {
+    /// The typed error code
+    pub fn error_code(&self) -> RpcErrorCode {
+        RpcErrorCode::from(self)
+    }
+
+    /// Returns whether the caller may try again, see `RpcErrorCode::is_retryable`
+    pub fn is_retryable(&self) -> bool {
+        self.error_code().is_retryable() || self.is_transient()
+    }
+
+    /// Returns whether lightningd could not handle the call yet (e.g. it is
+    /// still syncing with bitcoind), so the very same call may succeed if sent
+    /// again later
+    pub fn is_transient(&self) -> bool {
+        self.error_code().is_transient()
+            || (self.error_code() == RpcErrorCode::Lightningd
+                && self.message.to_lowercase().contains("still syncing"))
+    }
+
+    /// Decode the `data` of the error according to its code
+    pub fn decoded_data(&self) -> Option<RpcErrorData> {
+        let data = self.data.as_ref()?;
+        let decoded = match self.error_code() {
+            RpcErrorCode::PayDestinationPermFail
+            | RpcErrorCode::PayTryOtherRoute
+            | RpcErrorCode::PayRouteNotFound
+            | RpcErrorCode::PayRouteTooExpensive
+            | RpcErrorCode::PayInvoiceExpired
+            | RpcErrorCode::PayUnparseableOnion
+            | RpcErrorCode::PayStoppedRetrying => serde_json::from_value(data.clone())
+                .ok()
+                .map(|failure| RpcErrorData::PayFailure(Box::new(failure))),
+            _ => None,
+        };
+        Some(decoded.unwrap_or_else(|| RpcErrorData::Other(data.clone())))
+    }
+
+    /// The details of the failed payment, if this is a payment error
+    pub fn pay_failure(&self) -> Option<PayFailure> {
+        match self.decoded_data()? {
+            RpcErrorData::PayFailure(failure) => Some(*failure),
+            RpcErrorData::Other(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{RpcError, RpcErrorCode, RpcErrorData};
+
+    #[test]
+    fn error_code_roundtrip() {
+        for code in [-32602, -1, 205, 210, 304, 907, 1003, 1202, 1502, 0x4c50, 42] {
+            assert_eq!(RpcErrorCode::from(code).code(), code);
+        }
+        assert_eq!(RpcErrorCode::from(205), RpcErrorCode::PayRouteNotFound);
+        assert_eq!(RpcErrorCode::from(42), RpcErrorCode::Unknown(42));
+    }
+
+    #[test]
+    fn error_codes_match_lightningd() {
+        // common/jsonrpc_errors.h of Core Lightning
+        let codes = [
+            (RpcErrorCode::InvalidRequest, -32600),
+            (RpcErrorCode::MethodNotFound, -32601),
+            (RpcErrorCode::InvalidParams, -32602),
+            (RpcErrorCode::Lightningd, -1),
+            (RpcErrorCode::ParamDevError, -2),
+            (RpcErrorCode::PluginError, -3),
+            (RpcErrorCode::PluginTerminated, -4),
+            (RpcErrorCode::LightningdShutdown, -5),
+            (RpcErrorCode::PayInProgress, 200),
+            (RpcErrorCode::PayRhashAlreadyUsed, 201),
+            (RpcErrorCode::PayUnparseableOnion, 202),
+            (RpcErrorCode::PayDestinationPermFail, 203),
+            (RpcErrorCode::PayTryOtherRoute, 204),
+            (RpcErrorCode::PayRouteNotFound, 205),
+            (RpcErrorCode::PayRouteTooExpensive, 206),
+            (RpcErrorCode::PayInvoiceExpired, 207),
+            (RpcErrorCode::PayNoSuchPayment, 208),
+            (RpcErrorCode::PayUnspecifiedError, 209),
+            (RpcErrorCode::PayStoppedRetrying, 210),
+            (RpcErrorCode::PayStatusUnexpected, 211),
+            (RpcErrorCode::PayInvoiceRequestInvalid, 212),
+            (RpcErrorCode::PayInvoicePreapprovalDeclined, 213),
+            (RpcErrorCode::PayKeysendPreapprovalDeclined, 214),
+            (RpcErrorCode::PayInjectPaymentOnionFailed, 218),
+            (RpcErrorCode::FundMaxExceeded, 300),
+            (RpcErrorCode::FundCannotAfford, 301),
+            (RpcErrorCode::FundOutputIsDust, 302),
+            (RpcErrorCode::FundingBroadcastFail, 303),
+            (RpcErrorCode::FundingStillSyncingBitcoin, 304),
+            (RpcErrorCode::FundingPeerNotConnected, 305),
+            (RpcErrorCode::FundingUnknownPeer, 306),
+            (RpcErrorCode::FundingNothingToCancel, 307),
+            (RpcErrorCode::FundingCancelNotSafe, 308),
+            (RpcErrorCode::FundingPsbtInvalid, 309),
+            (RpcErrorCode::FundingV2NotSupported, 310),
+            (RpcErrorCode::FundingUnknownChannel, 311),
+            (RpcErrorCode::FundingStateInvalid, 312),
+            (RpcErrorCode::FundCannotAffordWithEmergency, 313),
+            (RpcErrorCode::SpliceBroadcastFail, 350),
+            (RpcErrorCode::SpliceWrongOwner, 351),
+            (RpcErrorCode::SpliceUnknownChannel, 352),
+            (RpcErrorCode::SpliceInvalidChannelState, 353),
+            (RpcErrorCode::SpliceNotSupported, 354),
+            (RpcErrorCode::SpliceBusyError, 355),
+            (RpcErrorCode::SpliceInputError, 356),
+            (RpcErrorCode::SpliceFundingLow, 357),
+            (RpcErrorCode::SpliceLowFee, 358),
+            (RpcErrorCode::SpliceHighFee, 359),
+            (RpcErrorCode::ConnectNoKnownAddress, 400),
+            (RpcErrorCode::ConnectAllAddressesFailed, 401),
+            (RpcErrorCode::ConnectDisconnectedDuring, 402),
+            (RpcErrorCode::BcliError, 500),
+            (RpcErrorCode::BcliNoFeeEstimates, 501),
+            (RpcErrorCode::HsmEcdhFailed, 800),
+            (RpcErrorCode::InvoiceLabelAlreadyExists, 900),
+            (RpcErrorCode::InvoicePreimageAlreadyExists, 901),
+            (RpcErrorCode::InvoiceHintsGaveNoRoutes, 902),
+            (RpcErrorCode::InvoiceExpiredDuringWait, 903),
+            (RpcErrorCode::InvoiceWaitTimedOut, 904),
+            (RpcErrorCode::InvoiceNotFound, 905),
+            (RpcErrorCode::InvoiceStatusUnexpected, 906),
+            (RpcErrorCode::InvoiceOfferInactive, 907),
+            (RpcErrorCode::InvoiceNoDescription, 908),
+            (RpcErrorCode::OfferAlreadyExists, 1000),
+            (RpcErrorCode::OfferAlreadyDisabled, 1001),
+            (RpcErrorCode::OfferExpired, 1002),
+            (RpcErrorCode::OfferRouteNotFound, 1003),
+            (RpcErrorCode::OfferBadInvreqReply, 1004),
+            (RpcErrorCode::OfferTimeout, 1005),
+            (RpcErrorCode::DatastoreDelDoesNotExist, 1200),
+            (RpcErrorCode::DatastoreDelWrongGeneration, 1201),
+            (RpcErrorCode::DatastoreUpdateAlreadyExists, 1202),
+            (RpcErrorCode::DatastoreUpdateDoesNotExist, 1203),
+            (RpcErrorCode::DatastoreUpdateWrongGeneration, 1204),
+            (RpcErrorCode::DatastoreUpdateHasChildren, 1205),
+            (RpcErrorCode::DatastoreUpdateNoChildren, 1206),
+            (RpcErrorCode::SignmessagePubkeyNotFound, 1301),
+            (RpcErrorCode::DelforwardNotFound, 1401),
+            (RpcErrorCode::RuneNotAuthorized, 1501),
+            (RpcErrorCode::RuneNotPermitted, 1502),
+            (RpcErrorCode::RuneBlacklisted, 1503),
+            (RpcErrorCode::WaitTimeout, 2000),
+            (RpcErrorCode::CommandoErrorRemote, 0x4c4f),
+            (RpcErrorCode::CommandoErrorRemoteAuth, 0x4c50),
+        ];
+        for (error_code, code) in codes {
+            assert_eq!(error_code.code(), code);
+            assert_eq!(RpcErrorCode::from(code), error_code);
+        }
+    }
+
+    #[test]
+    fn decode_pay_failure() {
+        let err: RpcError = serde_json::from_value(json!({
+            "code": 204,
+            "message": "failed: WIRE_TEMPORARY_CHANNEL_FAILURE",
+            "data": {
+                "id": 2,
+                "payment_hash": "00",
+                "status": "failed",
+                "erring_index": 1,
+                "failcode": 4103,
+                "failcodename": "WIRE_TEMPORARY_CHANNEL_FAILURE",
+                "erring_node": "02",
+                "erring_channel": "103x1x0",
+                "erring_direction": 0
+            }
+        }))
+        .unwrap();
+        assert!(err.is_retryable());
+        assert!(!err.is_transient());
+        let failure = err.pay_failure().unwrap();
+        assert_eq!(failure.failcode, Some(4103));
+        assert_eq!(failure.erring_channel.as_deref(), Some("103x1x0"));
+
+        let err = RpcError {
+            code: 1202,
+            message: "already exists".to_owned(),
+            data: Some(json!({"key": ["a"]})),
+        };
+        assert!(err.error_code().is_datastore_error());
+        assert_eq!(
+            err.decoded_data(),
+            Some(RpcErrorData::Other(json!({"key": ["a"]})))
+        );
+    }
+}
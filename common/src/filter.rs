@@ -0,0 +1,338 @@
+//! Response filters
+//!
+//! Core Lightning accepts a `filter` object in the request that trims the
+//! response to the fields listed in it. This module derives such a filter
+//! from the type the response is decoded into, so only the fields known
+//! by the type are sent over the socket.
+use std::collections::BTreeMap;
+
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+/// Maximum nesting of the structures traced, to stop on recursive types
+const MAX_DEPTH: usize = 32;
+
+/// Fields of the response read by a type
+#[derive(Debug)]
+enum Node {
+    /// The whole value is needed
+    All,
+    /// Only some fields of an object are needed
+    Object(BTreeMap<&'static str, Node>),
+    /// The filter applies to each item of an array
+    Array(Box<Node>),
+}
+
+impl Node {
+    fn into_filter(self) -> Value {
+        match self {
+            Node::All => Value::Bool(true),
+            Node::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, node)| (name.to_owned(), node.into_filter()))
+                    .collect(),
+            ),
+            Node::Array(item) => Value::Array(vec![item.into_filter()]),
+        }
+    }
+}
+
+/// Derive the `filter` that keeps only the fields read by `T`.
+///
+/// Returns `None` when `T` reads the whole response (e.g. `serde_json::Value`
+/// or a map), in which case no filter should be sent.
+///
+/// The filter is built from the layout `T` declares to serde, when part of
+/// the layout can not be traced (e.g. untagged enums) the whole field is kept.
+pub fn filter_for<T: DeserializeOwned>() -> Option<Value> {
+    let mut root = Node::All;
+    // the value produced is meaningless, only the traced fields matter
+    let _ = T::deserialize(Tracer {
+        node: &mut root,
+        depth: 0,
+    });
+    match root {
+        Node::Object(_) => Some(root.into_filter()),
+        _ => None,
+    }
+}
+
+/// Deserializer that records the fields requested by the type, feeding it
+/// placeholder values.
+struct Tracer<'a> {
+    node: &'a mut Node,
+    depth: usize,
+}
+
+impl<'de> de::Deserializer<'de> for Tracer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_char('0')
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_str("")
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_bytes(&[])
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::Array(Box::new(Node::All));
+        let Node::Array(item) = self.node else {
+            unreachable!();
+        };
+        visitor.visit_seq(SeqTracer {
+            item: Some(item),
+            depth: self.depth,
+        })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        visitor.visit_map(StructTracer {
+            fields: [].iter(),
+            current: None,
+            node: &mut BTreeMap::new(),
+            depth: self.depth,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if self.depth >= MAX_DEPTH {
+            *self.node = Node::All;
+            return Err(de::Error::custom("structure too deep"));
+        }
+        // all the fields are needed until proven otherwise, so the filter
+        // stays valid when tracing stops early.
+        *self.node = Node::Object(fields.iter().map(|field| (*field, Node::All)).collect());
+        let Node::Object(node) = self.node else {
+            unreachable!();
+        };
+        visitor.visit_map(StructTracer {
+            fields: fields.iter(),
+            current: None,
+            node,
+            depth: self.depth,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.node = Node::All;
+        Err(de::Error::custom("enums can not be traced"))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        tuple tuple_struct
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+}
+
+/// Feeds a single traced item to a sequence
+struct SeqTracer<'a> {
+    item: Option<&'a mut Box<Node>>,
+    depth: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqTracer<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let Some(item) = self.item.take() else {
+            return Ok(None);
+        };
+        seed.deserialize(Tracer {
+            node: item,
+            depth: self.depth + 1,
+        })
+        .map(Some)
+    }
+}
+
+/// Feeds all the declared fields of a structure, tracing their values
+struct StructTracer<'a> {
+    fields: std::slice::Iter<'static, &'static str>,
+    current: Option<&'static str>,
+    node: &'a mut BTreeMap<&'static str, Node>,
+    depth: usize,
+}
+
+impl<'de> de::MapAccess<'de> for StructTracer<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some(field) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.current = Some(field);
+        let key: StrDeserializer<Error> = field.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let field = self
+            .current
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before its key"))?;
+        let node = self.node.entry(field).or_insert(Node::All);
+        seed.deserialize(Tracer {
+            node,
+            depth: self.depth + 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use super::filter_for;
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    struct Channel {
+        short_channel_id: String,
+        #[serde(rename = "type")]
+        type_: Option<String>,
+        htlcs: Vec<Value>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize)]
+    struct ListChannels {
+        channels: Vec<Channel>,
+        configs: HashMap<String, Value>,
+    }
+
+    #[test]
+    fn derive_nested_filter() {
+        assert_eq!(
+            filter_for::<ListChannels>(),
+            Some(json!({
+                "channels": [{"short_channel_id": true, "type": true, "htlcs": [true]}],
+                "configs": true,
+            }))
+        );
+        assert_eq!(filter_for::<Value>(), None);
+        assert_eq!(filter_for::<HashMap<String, Value>>(), None);
+    }
+}
@@ -6,8 +6,12 @@ extern crate serde;
 extern crate serde_json;
 
 pub mod client;
+pub mod commando;
 pub mod errors;
+pub mod filter;
 pub mod json_utils;
+pub mod retry;
+pub mod transport;
 pub mod types;
 
 #[cfg(feature = "async")]
@@ -0,0 +1,246 @@
+//! Retry support
+//!
+//! A `RetryPolicy` set on the clients repeats the requests that fail for
+//! a transient reason, e.g. lightningd still starting or syncing with
+//! bitcoind, waiting longer after each attempt.
+//!
+//! Only the commands known to be read-only (e.g. `listfunds`) are retried,
+//! all the others (e.g. `pay`, `withdraw` or the commands of unknown plugins)
+//! are sent once.
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::types::Response;
+
+/// Commands that only read the state of the node, and so can be repeated
+/// when their outcome is unknown
+const READ_ONLY: &[&str] = &[
+    "askrene-listlayers",
+    "bkpr-channelsapy",
+    "bkpr-inspect",
+    "bkpr-listaccountevents",
+    "bkpr-listbalances",
+    "bkpr-listincome",
+    "checkmessage",
+    "checkrune",
+    "datastoreusage",
+    "decode",
+    "decodepay",
+    "feerates",
+    "getemergencyrecoverdata",
+    "getinfo",
+    "getlog",
+    "getroute",
+    "getroutes",
+    "help",
+    "listaddresses",
+    "listchannels",
+    "listclosedchannels",
+    "listconfigs",
+    "listdatastore",
+    "listforwards",
+    "listfunds",
+    "listhtlcs",
+    "listinvoicerequests",
+    "listinvoices",
+    "listnodes",
+    "listoffers",
+    "listpays",
+    "listpeerchannels",
+    "listpeers",
+    "listsendpays",
+    "listsqlschemas",
+    "listtransactions",
+    "makesecret",
+    "showrunes",
+    "signmessage",
+    "sql",
+    "staticbackup",
+    "wait",
+    "waitanyinvoice",
+    "waitblockheight",
+    "waitinvoice",
+    "waitsendpay",
+];
+
+/// Returns whether the command can be repeated without side effects
+/// when its outcome is unknown.
+///
+/// Only the read-only commands known by this library are, any other command
+/// (including the ones of plugins) is sent once.
+pub fn is_idempotent(method: &str) -> bool {
+    READ_ONLY.binary_search(&method).is_ok()
+}
+
+/// Returns whether the request failed for a reason that may go away by
+/// itself, i.e. lightningd is not listening yet, the connection was dropped
+/// or lightningd reports a transient error (see `RpcError::is_transient`).
+///
+/// The errors a caller may retry with other parameters or on purpose, such
+/// as a failed payment or the timeout of `wait`, are not transient.
+pub fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Io(err) => matches!(
+            err.kind(),
+            io::ErrorKind::NotFound
+                | io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof
+        ),
+        Error::Rpc(err) => err.is_transient(),
+        _ => false,
+    }
+}
+
+/// Predicate deciding if a failed request is retried
+#[derive(Clone)]
+struct Predicate(Arc<dyn Fn(&Error) -> bool + Send + Sync>);
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Predicate")
+    }
+}
+
+/// How many times, and how often, a failed request is retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// maximum number of attempts, including the first one
+    max_attempts: u32,
+    /// delay before the first retry, doubled at each following retry
+    initial_backoff: Duration,
+    /// upper bound of the delay between two attempts
+    max_backoff: Duration,
+    /// decides which errors are retried
+    predicate: Predicate,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(5)
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new policy making up to `max_attempts` attempts, waiting
+    /// from 100 milliseconds up to 5 seconds between them, and retrying
+    /// the transient errors, see `is_transient`.
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            predicate: Predicate(Arc::new(is_transient)),
+        }
+    }
+
+    /// Set the delay before the first retry, doubled at each following
+    /// retry up to `max`.
+    pub fn set_backoff(&mut self, initial: Duration, max: Duration) {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+    }
+
+    /// Set the predicate deciding which errors are retried
+    pub fn set_predicate<F>(&mut self, predicate: F)
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Predicate(Arc::new(predicate));
+    }
+
+    /// Maximum number of attempts, including the first one
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay to wait after the failed attempt number `attempt`, starting from 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Returns whether the request is retried after the failed attempt
+    /// number `attempt`, starting from 1.
+    pub fn should_retry(&self, attempt: u32, err: &Error) -> bool {
+        attempt < self.max_attempts && (self.predicate.0)(err)
+    }
+
+    /// Returns whether the request is retried after the attempt number
+    /// `attempt` got `response`, an error or a response carrying an error.
+    pub(crate) fn should_retry_response<D>(
+        &self,
+        attempt: u32,
+        response: &Result<Response<D>, Error>,
+    ) -> bool {
+        match response {
+            Ok(Response {
+                error: Some(err), ..
+            }) => self.should_retry(attempt, &Error::Rpc(err.clone())),
+            Ok(_) => false,
+            Err(err) => self.should_retry(attempt, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use super::{is_idempotent, RetryPolicy, READ_ONLY};
+    use crate::errors::{Error, RpcError};
+
+    #[test]
+    fn exponential_backoff() {
+        let mut policy = RetryPolicy::new(4);
+        policy.set_backoff(Duration::from_millis(10), Duration::from_millis(50));
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(4), Duration::from_millis(50));
+        assert_eq!(policy.backoff(100), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn retry_transient_errors() {
+        let policy = RetryPolicy::new(3);
+        let refused = Error::Io(io::Error::from(io::ErrorKind::ConnectionRefused));
+        assert!(policy.should_retry(1, &refused));
+        assert!(!policy.should_retry(3, &refused));
+        let syncing = Error::Rpc(RpcError {
+            code: -1,
+            message: "Still syncing with bitcoin network".to_owned(),
+            data: None,
+        });
+        assert!(policy.should_retry(1, &syncing));
+        let failed = Error::Rpc(RpcError {
+            code: -1,
+            message: "Unknown peer".to_owned(),
+            data: None,
+        });
+        assert!(!policy.should_retry(1, &failed));
+        let timeout = Error::Rpc(RpcError {
+            code: 2000,
+            message: "Timed out".to_owned(),
+            data: None,
+        });
+        assert!(!policy.should_retry(1, &timeout));
+
+        assert!(is_idempotent("listfunds"));
+        assert!(is_idempotent("bkpr-listbalances"));
+        assert!(!is_idempotent("pay"));
+        assert!(!is_idempotent("withdraw"));
+        assert!(!is_idempotent("setconfig"));
+        assert!(!is_idempotent("askrene-create-layer"));
+        assert!(!is_idempotent("someplugin-command"));
+        // is_idempotent() searches the sorted list
+        assert!(READ_ONLY.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,417 @@
+//! Transport support
+//!
+//! A transport carries the requests to lightningd and brings back its
+//! responses. `client::Client` is the default transport, talking to the
+//! lightning-rpc socket over a UNIX or TCP socket, `commando::Commando` reaches
+//! a remote node, and the transports defined here allow to test code
+//! without a running lightningd.
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client::Client;
+use crate::commando::Commando;
+use crate::errors::{Error, RpcError, RpcErrorCode};
+use crate::types::{BatchRequest, BatchResponse, Id, Response};
+
+/// A way to send requests to lightningd and receive its responses
+pub trait Transport {
+    /// Sends a request, returning the response of lightningd.
+    ///
+    /// When `filter` is set, the response is trimmed to the fields listed
+    /// in it, see the `filter` module.
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error>;
+
+    /// Sends a request that must not be repeated (e.g. `pay`), so it is
+    /// never retried by the transport.
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        self.send(method, params, filter)
+    }
+
+    /// Sends all the calls of the batch, and waits for all their responses.
+    ///
+    /// By default the calls are sent one after the other.
+    fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        let mut responses = Vec::with_capacity(batch.len());
+        for (method, params) in batch.calls() {
+            responses.push(self.send(method, params.clone(), None)?);
+        }
+        Ok(BatchResponse::new(batch, responses))
+    }
+}
+
+impl Transport for Client {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        self.send_request_with_filter(method, params, filter)
+    }
+
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        Client::send_once(self, method, params, filter)
+    }
+
+    fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        Client::send_batch(self, batch)
+    }
+}
+
+impl Transport for Commando {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        self.send_request_with_filter(method, params, filter)
+    }
+
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        Commando::send_once(self, method, params, filter)
+    }
+
+    fn send_batch(&self, batch: &BatchRequest) -> Result<BatchResponse, Error> {
+        Commando::send_batch(self, batch)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// In memory transport answering with the results queued for each method.
+///
+/// A request to a method without queued results fails as an unknown
+/// command, as lightningd would do.
+#[derive(Debug, Default)]
+pub struct Mock {
+    /// results queued for each method, returned in order
+    results: Mutex<HashMap<String, VecDeque<Result<Value, RpcError>>>>,
+    /// requests received so far
+    requests: Mutex<Vec<(String, Value)>>,
+    /// counter used to generate the id of the next response
+    next_id: AtomicU64,
+}
+
+impl Mock {
+    /// Creates a new mock without any result queued
+    pub fn new() -> Mock {
+        Mock::default()
+    }
+
+    /// Queue the result of the next call to `method`
+    pub fn expect(&self, method: &str, result: Value) {
+        self.push(method, Ok(result));
+    }
+
+    /// Queue the error returned by the next call to `method`
+    pub fn expect_error(&self, method: &str, error: RpcError) {
+        self.push(method, Err(error));
+    }
+
+    fn push(&self, method: &str, result: Result<Value, RpcError>) {
+        lock(&self.results)
+            .entry(method.to_owned())
+            .or_default()
+            .push_back(result);
+    }
+
+    /// Requests received so far, with their params
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        lock(&self.requests).clone()
+    }
+
+    /// Build the response to a request to `method`
+    pub(crate) fn respond(&self, method: &str, params: Value) -> Response<Value> {
+        lock(&self.requests).push((method.to_owned(), params));
+        let result = lock(&self.results)
+            .get_mut(method)
+            .and_then(|results| results.pop_front())
+            .unwrap_or_else(|| {
+                Err(RpcError {
+                    code: RpcErrorCode::MethodNotFound.code(),
+                    message: format!("Unknown command '{method}'"),
+                    data: None,
+                })
+            });
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Response {
+            result,
+            error,
+            id: Id::Str(format!("mock/{id}")),
+            jsonrpc: Some("2.0".to_owned()),
+        }
+    }
+}
+
+impl Transport for Mock {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        _filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        Ok(self.respond(method, params))
+    }
+}
+
+/// A request together with the response of lightningd, as stored by
+/// `Recorder` and served back by `Replay`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    /// method of the request
+    pub method: String,
+    /// params of the request
+    pub params: Value,
+    /// response of lightningd to the request
+    pub response: Response<Value>,
+}
+
+/// Transport recording all the requests sent through another transport
+/// together with their responses, so they can be served back by `Replay`.
+#[derive(Debug)]
+pub struct Recorder<T> {
+    inner: T,
+    exchanges: Mutex<Vec<Exchange>>,
+}
+
+impl<T> Recorder<T> {
+    /// Creates a new recorder sending the requests through `inner`
+    pub fn new(inner: T) -> Recorder<T> {
+        Recorder {
+            inner,
+            exchanges: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Get reference to the transport used to send the requests
+    pub fn inner(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Exchanges recorded so far
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        lock(&self.exchanges).clone()
+    }
+
+    /// Write the exchanges recorded so far to the JSON file at `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(file, &*lock(&self.exchanges))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn inner_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub(crate) fn record(&self, method: &str, params: Value, response: &Response<Value>) {
+        lock(&self.exchanges).push(Exchange {
+            method: method.to_owned(),
+            params,
+            response: response.clone(),
+        });
+    }
+}
+
+impl<T: Transport> Transport for Recorder<T> {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        let response = self.inner.send(method, params.clone(), filter)?;
+        self.record(method, params, &response);
+        Ok(response)
+    }
+
+    fn send_once(
+        &self,
+        method: &str,
+        params: Value,
+        filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        let response = self.inner.send_once(method, params.clone(), filter)?;
+        self.record(method, params, &response);
+        Ok(response)
+    }
+}
+
+/// Transport serving back the exchanges recorded by `Recorder`.
+///
+/// The requests must arrive in the same order, with the same params,
+/// as they were recorded.
+#[derive(Debug)]
+pub struct Replay {
+    exchanges: Mutex<VecDeque<Exchange>>,
+}
+
+impl Replay {
+    /// Creates a new replay serving `exchanges` in order
+    pub fn new(exchanges: Vec<Exchange>) -> Replay {
+        Replay {
+            exchanges: Mutex::new(exchanges.into()),
+        }
+    }
+
+    /// Load the exchanges saved by `Recorder::save` from the file at `path`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Replay, Error> {
+        let file = BufReader::new(File::open(path)?);
+        let exchanges: Vec<Exchange> = serde_json::from_reader(file)?;
+        Ok(Replay::new(exchanges))
+    }
+
+    /// Number of exchanges not served yet
+    pub fn remaining(&self) -> usize {
+        lock(&self.exchanges).len()
+    }
+
+    pub(crate) fn respond(&self, method: &str, params: Value) -> Result<Response<Value>, Error> {
+        let mut exchanges = lock(&self.exchanges);
+        let Some(exchange) = exchanges.front() else {
+            return Err(unexpected_request(method, &params, "no more requests"));
+        };
+        if exchange.method != method || exchange.params != params {
+            let expected = json!({ "method": exchange.method, "params": exchange.params });
+            return Err(unexpected_request(method, &params, &expected.to_string()));
+        }
+        exchanges
+            .pop_front()
+            .map(|exchange| exchange.response)
+            .ok_or(Error::NoErrorOrResult)
+    }
+}
+
+fn unexpected_request(method: &str, params: &Value, expected: &str) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unexpected request `{method}` with params {params}, expected {expected}"),
+    ))
+}
+
+impl Transport for Replay {
+    fn send(
+        &self,
+        method: &str,
+        params: Value,
+        _filter: Option<Value>,
+    ) -> Result<Response<Value>, Error> {
+        self.respond(method, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Mock, Recorder, Replay, Transport};
+    use crate::errors::{Error, RpcErrorCode};
+    use crate::types::BatchRequest;
+
+    #[test]
+    fn mock_answers_in_order() {
+        let mock = Mock::new();
+        mock.expect("getinfo", json!({ "id": "02aa" }));
+        mock.expect("getinfo", json!({ "id": "02bb" }));
+
+        let first = mock.send("getinfo", json!({}), None).unwrap();
+        assert_eq!(first.into_result().unwrap(), json!({ "id": "02aa" }));
+        let second = mock.send("getinfo", json!({}), None).unwrap();
+        assert_eq!(second.into_result().unwrap(), json!({ "id": "02bb" }));
+
+        let unknown = mock.send("getinfo", json!({}), None).unwrap();
+        match unknown.into_result() {
+            Err(Error::Rpc(err)) => {
+                assert_eq!(err.error_code(), RpcErrorCode::MethodNotFound)
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[test]
+    fn replay_recorded_exchanges() {
+        let mock = Mock::new();
+        mock.expect("listfunds", json!({ "outputs": [] }));
+        mock.expect("getinfo", json!({ "id": "02aa" }));
+        let recorder = Recorder::new(mock);
+        let mut batch = BatchRequest::new();
+        let funds = batch
+            .add::<_, serde_json::Value>("listfunds", json!({}))
+            .unwrap();
+        let info = batch
+            .add::<_, serde_json::Value>("getinfo", json!({}))
+            .unwrap();
+        let mut results = recorder.send_batch(&batch).unwrap();
+        assert_eq!(results.take(info).unwrap(), json!({ "id": "02aa" }));
+
+        let replay = Replay::new(recorder.exchanges());
+        let mut results = replay.send_batch(&batch).unwrap();
+        assert_eq!(results.take(funds).unwrap(), json!({ "outputs": [] }));
+        assert_eq!(replay.remaining(), 0);
+
+        let replay = Replay::new(recorder.exchanges());
+        assert!(replay.send("getinfo", json!({}), None).is_err());
+    }
+
+    #[test]
+    fn batch_entries_belong_to_their_batch() {
+        let mock = Mock::new();
+        mock.expect("getinfo", json!({ "id": "02aa" }));
+        mock.expect("listfunds", json!({ "outputs": [] }));
+        let mut first = BatchRequest::new();
+        let info = first
+            .add::<_, serde_json::Value>("getinfo", json!({}))
+            .unwrap();
+        let mut second = BatchRequest::new();
+        let funds = second
+            .add::<_, serde_json::Value>("listfunds", json!({}))
+            .unwrap();
+        mock.send_batch(&first).unwrap();
+        let mut second_results = mock.send_batch(&second).unwrap();
+
+        assert!(matches!(
+            second_results.take(info),
+            Err(Error::BatchMismatch)
+        ));
+        assert_eq!(
+            second_results.take(funds).unwrap(),
+            json!({ "outputs": [] })
+        );
+    }
+}
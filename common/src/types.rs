@@ -2,11 +2,15 @@
 /// https://www.jsonrpc.org/specification
 ///
 /// author: https://github.com/vincenzopalazzo
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::errors::{Error, RpcError};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Id {
     Str(String),
@@ -38,10 +42,13 @@ pub struct Request<T: Serialize> {
     pub id: Option<Id>,
     /// jsonrpc field, MUST be "2.0"
     pub jsonrpc: String,
+    /// Fields of the response to return, see the `filter` module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 /// A standard JSONRPC response object
 pub struct Response<T> {
     /// A result if there is one, or null
@@ -69,3 +76,171 @@ impl<T> Response<T> {
         self.result.is_none()
     }
 }
+
+impl<T: DeserializeOwned> Response<T> {
+    /// Decode a response received from lightningd, checking the `jsonrpc` version
+    pub(crate) fn from_message(message: serde_json::Value) -> Result<Self, Error> {
+        let response: Response<T> = serde_json::from_value(message)?;
+        if response
+            .jsonrpc
+            .as_ref()
+            .is_some_and(|version| version != "2.0")
+        {
+            return Err(Error::VersionMismatch);
+        }
+        Ok(response)
+    }
+}
+
+/// A notification sent by lightningd on the RPC socket, while a
+/// command is running, once notifications are enabled on the client.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "lowercase")]
+pub enum Notification {
+    /// A log message about a running command
+    Message(MessageNotification),
+    /// The progress of a running command
+    Progress(ProgressNotification),
+}
+
+/// Content of the `message` notification
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MessageNotification {
+    /// Id of the request that generated the message
+    pub id: Option<Id>,
+    /// Log level of the message
+    pub level: String,
+    pub message: String,
+}
+
+/// Content of the `progress` notification
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProgressNotification {
+    /// Id of the request whose progress is reported
+    pub id: Option<Id>,
+    pub num: u64,
+    pub total: u64,
+    /// Progress of the current stage, for commands running in multiple stages
+    pub stage: Option<ProgressStage>,
+}
+
+/// Progress of a stage in the `progress` notification
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProgressStage {
+    pub num: u64,
+    pub total: u64,
+}
+
+/// A batch of calls that are sent together to lightningd
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// identifies the batch, so that its entries can not be taken from
+    /// the response to another batch
+    pub(crate) id: u64,
+    pub(crate) calls: Vec<(String, serde_json::Value)>,
+}
+
+impl Default for BatchRequest {
+    fn default() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        BatchRequest {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            calls: Vec::new(),
+        }
+    }
+}
+
+impl BatchRequest {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a call to the batch, returning the handle used to take its
+    /// result from the `BatchResponse`.
+    pub fn add<S: Serialize, D: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: S,
+    ) -> Result<BatchEntry<D>, Error> {
+        let params = serde_json::to_value(params)?;
+        self.calls.push((method.to_owned(), params));
+        Ok(BatchEntry {
+            batch: self.id,
+            index: self.calls.len() - 1,
+            _result: PhantomData,
+        })
+    }
+
+    /// The calls in the batch, as `(method, params)` pairs
+    pub fn calls(&self) -> &[(String, serde_json::Value)] {
+        &self.calls
+    }
+
+    /// Returns the number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether the batch contains no calls
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+}
+
+/// Handle to the result of a call added to a `BatchRequest`
+#[derive(Debug)]
+pub struct BatchEntry<D> {
+    batch: u64,
+    index: usize,
+    _result: PhantomData<fn() -> D>,
+}
+
+impl<D> BatchEntry<D> {
+    /// Position of the call in the batch
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// The responses to a `BatchRequest`, in the same order as the calls
+#[derive(Debug)]
+pub struct BatchResponse {
+    batch: u64,
+    responses: Vec<Option<Response<serde_json::Value>>>,
+}
+
+impl BatchResponse {
+    pub(crate) fn new(batch: &BatchRequest, responses: Vec<Response<serde_json::Value>>) -> Self {
+        BatchResponse {
+            batch: batch.id,
+            responses: responses.into_iter().map(Some).collect(),
+        }
+    }
+
+    /// Take the typed result of a call out of the batch.
+    ///
+    /// An error returned by lightningd for this call does not affect the
+    /// other calls of the batch. An entry of another batch is rejected
+    /// with `Error::BatchMismatch`.
+    pub fn take<D: DeserializeOwned>(&mut self, entry: BatchEntry<D>) -> Result<D, Error> {
+        if entry.batch != self.batch {
+            return Err(Error::BatchMismatch);
+        }
+        let response = self
+            .responses
+            .get_mut(entry.index)
+            .and_then(Option::take)
+            .ok_or(Error::NoErrorOrResult)?;
+        Ok(serde_json::from_value(response.into_result()?)?)
+    }
+
+    /// Returns the results of all the calls in order, the calls already
+    /// taken are reported as `Error::NoErrorOrResult`.
+    pub fn into_results(self) -> Vec<Result<serde_json::Value, Error>> {
+        self.responses
+            .into_iter()
+            .map(|response| response.ok_or(Error::NoErrorOrResult)?.into_result())
+            .collect()
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "bkpr")]
+mod bkpr;
+mod datastore;
 mod lightningrpc;
 
-pub use lightningrpc::LightningRPC;
+pub use datastore::Datastore;
+pub use lightningrpc::{Changes, LightningRPC};
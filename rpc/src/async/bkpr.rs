@@ -0,0 +1,142 @@
+//! Async bindings for the commands of the bookkeeper plugin, see `crate::bkpr`
+use clightningrpc_common::r#async::Transport;
+
+use super::LightningRPC;
+use crate::bkpr::{requests, responses, CsvFormat};
+use crate::errors::Error;
+
+impl<C: Transport> LightningRPC<C> {
+    /// Show the current balance of every account, the wallet and each channel.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_listbalances(&self) -> Result<responses::ListBalances, Error> {
+        self.call("bkpr-listbalances", requests::ListBalances {})
+            .await
+    }
+
+    /// Show the coin movements of {account} (or of all accounts, if no {account}),
+    /// only those of the payment {payment_id} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_listaccountevents(
+        &self,
+        account: Option<&str>,
+        payment_id: Option<&str>,
+    ) -> Result<responses::ListAccountEvents, Error> {
+        self.call(
+            "bkpr-listaccountevents",
+            requests::ListAccountEvents {
+                account,
+                payment_id,
+            },
+        )
+        .await
+    }
+
+    /// Show the income events (credits and debits of the node, transfers between
+    /// its accounts left out) between {start_time} and {end_time}, merging the
+    /// on-chain fees of each transaction if {consolidate_fees} (the default).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_listincome(
+        &self,
+        consolidate_fees: Option<bool>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::ListIncome, Error> {
+        self.call(
+            "bkpr-listincome",
+            requests::ListIncome {
+                consolidate_fees,
+                start_time,
+                end_time,
+            },
+        )
+        .await
+    }
+
+    /// Show the routing statistics and APY of each channel between {start_time}
+    /// and {end_time}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_channelsapy(
+        &self,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::ChannelsApy, Error> {
+        self.call(
+            "bkpr-channelsapy",
+            requests::ChannelsApy {
+                start_time,
+                end_time,
+            },
+        )
+        .await
+    }
+
+    /// Write the income events (see bkpr_listincome()) to {csv_file} in {csv_format},
+    /// the plugin picks the file name if no {csv_file}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_dumpincomecsv(
+        &self,
+        csv_format: CsvFormat,
+        csv_file: Option<&str>,
+        consolidate_fees: Option<bool>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::DumpIncomeCsv, Error> {
+        self.call(
+            "bkpr-dumpincomecsv",
+            requests::DumpIncomeCsv {
+                csv_format,
+                csv_file,
+                consolidate_fees,
+                start_time,
+                end_time,
+            },
+        )
+        .await
+    }
+
+    /// Show the on-chain transactions of the channel {account}, with their fees
+    /// and outputs.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_inspect(&self, account: &str) -> Result<responses::Inspect, Error> {
+        self.call("bkpr-inspect", requests::Inspect { account })
+            .await
+    }
+
+    /// Set the {description} of the events of the payment {payment_id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn bkpr_editdescriptionbypaymentid(
+        &self,
+        payment_id: &str,
+        description: &str,
+    ) -> Result<responses::EditDescriptionByPaymentId, Error> {
+        self.call(
+            "bkpr-editdescriptionbypaymentid",
+            requests::EditDescriptionByPaymentId {
+                payment_id,
+                description,
+            },
+        )
+        .await
+    }
+}
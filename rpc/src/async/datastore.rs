@@ -0,0 +1,101 @@
+//! Typed async interface to the lightningd datastore, see `crate::datastore`
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use clightningrpc_common::r#async::{Client, Transport};
+
+use super::LightningRPC;
+use crate::datastore::{entry, generation_of, key, names, Entry};
+use crate::errors::Error;
+use crate::requests::{DatastoreData, DatastoreMode};
+
+/// Values of type `T` stored under a key prefix of the datastore
+#[derive(Debug)]
+pub struct Datastore<'a, T, C = Client> {
+    rpc: &'a LightningRPC<C>,
+    prefix: Vec<String>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, C> Datastore<'a, T, C>
+where
+    T: Serialize + DeserializeOwned,
+    C: Transport,
+{
+    /// Creates a datastore keeping its values under the key {prefix}
+    pub fn new(rpc: &'a LightningRPC<C>, prefix: &[&str]) -> Datastore<'a, T, C> {
+        Datastore {
+            rpc,
+            prefix: prefix.iter().map(|key| key.to_string()).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Read the value {name}, `None` if it does not exist
+    #[allow(deprecated)]
+    pub async fn get(&self, name: &str) -> Result<Option<Entry<T>>, Error> {
+        let key = key(&self.prefix, name);
+        let entries = self.rpc.listdatastore(Some(&key)).await?.datastore;
+        entry(&key, entries)
+    }
+
+    /// Store {value} as {name}, replacing the current value if any,
+    /// and return the new generation
+    pub async fn set(&self, name: &str, value: &T) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::CreateOrReplace, None)
+            .await
+    }
+
+    /// Store {value} as {name}, failing if it already exists,
+    /// and return the new generation
+    pub async fn create(&self, name: &str, value: &T) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::MustCreate, None)
+            .await
+    }
+
+    /// Replace {name} with {value} only if it is still at {generation},
+    /// and return the new generation
+    pub async fn replace(&self, name: &str, value: &T, generation: u64) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::MustReplace, Some(generation))
+            .await
+    }
+
+    /// Remove {name}, only if it is still at {generation} if given
+    #[allow(deprecated)]
+    pub async fn remove(&self, name: &str, generation: Option<u64>) -> Result<(), Error> {
+        self.rpc
+            .deldatastore(&key(&self.prefix, name), generation)
+            .await
+            .map(|_| ())
+    }
+
+    /// Names of the values stored under the prefix
+    #[allow(deprecated)]
+    pub async fn names(&self) -> Result<Vec<String>, Error> {
+        let entries = self.rpc.listdatastore(Some(&self.prefix)).await?.datastore;
+        Ok(names(&self.prefix, entries))
+    }
+
+    #[allow(deprecated)]
+    async fn store(
+        &self,
+        name: &str,
+        value: &T,
+        mode: DatastoreMode,
+        generation: Option<u64>,
+    ) -> Result<u64, Error> {
+        let value = serde_json::to_string(value)?;
+        let stored = self
+            .rpc
+            .datastore(
+                &key(&self.prefix, name),
+                DatastoreData::String(&value),
+                Some(mode),
+                generation,
+            )
+            .await?;
+        generation_of(stored.generation)
+    }
+}
@@ -1,22 +1,95 @@
 //! High-level async interface to c-lightning RPC
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
-use crate::types::RouteItem;
-use clightningrpc_common::r#async::Client;
+use crate::types::{
+    AskreneInform, CustomMessage, Hex, PluginSubcommand, Psbt, RouteItem, WaitIndex, WaitSubsystem,
+};
+use clightningrpc_common::filter::filter_for;
+use clightningrpc_common::r#async::{Client, Transport};
+use clightningrpc_common::types::{BatchEntry, BatchRequest, BatchResponse};
 
 use crate::errors::Error;
-use crate::lightningrpc::PayOptions;
+use crate::lightningrpc::{
+    AskreneUpdateChannelOptions, FetchInvoiceOptions, FundChannelStartOptions, FundPsbtOptions,
+    GetRoutesOptions, InvoiceRequestOptions, KeysendOptions, ListForwardsOptions,
+    MultiFundChannelOptions, OfferOptions, OpenChannelOptions, PayOptions, SetChannelOptions,
+    UtxoPsbtOptions, XPayOptions, MAX_UPDATE_ROUNDS,
+};
 use crate::requests;
 use crate::responses;
+use crate::rune::Restriction;
 
 /// Structure providing a high-level async interface to the c-lightning daemon RPC
+///
+/// The requests are sent through the transport `T`, by default the
+/// lightning-rpc socket, see `clightningrpc_common::r#async::Transport`.
 #[derive(Debug)]
-pub struct LightningRPC {
-    client: Client,
+pub struct LightningRPC<T = Client> {
+    client: T,
+    /// Send a `filter` derived from the response type with every call
+    filter_responses: bool,
+}
+
+/// Builder for a batch of calls sent together to lightningd, see `LightningRPC::batch`
+#[derive(Debug)]
+pub struct Batch<'a, C = Client> {
+    client: &'a C,
+    request: BatchRequest,
+}
+
+impl<C: Transport> Batch<'_, C> {
+    /// Add a call to the batch, returning the handle used to take its result
+    /// from the `BatchResponse`.
+    pub fn call<T: Serialize, U: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        input: T,
+    ) -> Result<BatchEntry<U>, Error> {
+        self.request.add(method, input)
+    }
+
+    /// Send all the calls of the batch and wait for their results.
+    pub async fn send(self) -> Result<BatchResponse, Error> {
+        self.client.send_batch(&self.request).await
+    }
+}
+
+/// Changes of a subsystem, received one after the other, see `LightningRPC::changes`
+///
+/// The changes made while no `next` call is pending are reported together,
+/// with the index jumping by more than one. Fetch the entries between the
+/// previous and the new index with the `list` command of the subsystem.
+#[derive(Debug)]
+pub struct Changes<'a, C = Client> {
+    rpc: &'a LightningRPC<C>,
+    subsystem: WaitSubsystem,
+    indexname: WaitIndex,
+    nextvalue: u64,
+}
+
+impl<C: Transport> Changes<'_, C> {
+    /// Wait for the next change of the subsystem.
+    #[allow(deprecated)]
+    pub async fn next(&mut self) -> Result<responses::Wait, Error> {
+        let change = self
+            .rpc
+            .wait(self.subsystem, self.indexname, self.nextvalue)
+            .await?;
+        let reached = change.index(self.indexname).unwrap_or(self.nextvalue);
+        self.nextvalue = reached.max(self.nextvalue) + 1;
+        Ok(change)
+    }
+
+    /// The index value the next change will reach.
+    pub fn nextvalue(&self) -> u64 {
+        self.nextvalue
+    }
 }
 
 impl LightningRPC {
@@ -25,20 +98,36 @@ impl LightningRPC {
     /// # Arguments
     ///
     /// * `sockpath` - Path of UNIX socket to connect to, by default lightningd will create a
-    /// socket named `.lightning/lightning-rpc` in the home directory of the user running
-    /// lightningd.
+    ///   socket named `.lightning/lightning-rpc` in the home directory of the user running
+    ///   lightningd.
     pub fn new<P: AsRef<Path>>(sockpath: P) -> LightningRPC {
-        LightningRPC {
-            client: Client::new(sockpath),
-        }
+        LightningRPC::with_transport(Client::new(sockpath))
     }
 
     /// Set an optional timeout for requests
     pub fn set_timeout(&mut self, timeout: Option<Duration>) {
         self.client.set_timeout(timeout);
     }
+}
+
+impl<C: Transport> LightningRPC<C> {
+    /// Create a new async interface sending the requests through `transport`,
+    /// e.g. a TCP socket, a remote node reached with commando or a mock.
+    pub fn with_transport(transport: C) -> LightningRPC<C> {
+        LightningRPC {
+            client: transport,
+            filter_responses: false,
+        }
+    }
+
+    /// Ask lightningd to return only the fields known by the response type of
+    /// each call, which makes large responses (e.g. `listchannels` or `listnodes`)
+    /// much smaller. See `clightningrpc_common::filter`.
+    pub fn set_filter_responses(&mut self, filter_responses: bool) {
+        self.filter_responses = filter_responses;
+    }
 
-    pub fn client(&mut self) -> &mut Client {
+    pub fn client(&mut self) -> &mut C {
         &mut self.client
     }
 
@@ -48,8 +137,58 @@ impl LightningRPC {
         method: &str,
         input: T,
     ) -> Result<U, Error> {
-        let response = self.client.send_request(method, input).await?;
-        response.into_result()
+        let filter = if self.filter_responses {
+            filter_for::<U>()
+        } else {
+            None
+        };
+        self.call_with_filter(method, input, filter).await
+    }
+
+    /// Generic call function for async RPC calls that must not be repeated
+    /// (e.g. a payment), so they are never retried by the transport.
+    pub async fn call_once<T: Serialize, U: DeserializeOwned>(
+        &self,
+        method: &str,
+        input: T,
+    ) -> Result<U, Error> {
+        let filter = if self.filter_responses {
+            filter_for::<U>()
+        } else {
+            None
+        };
+        let params = serde_json::to_value(input)?;
+        let result = self
+            .client
+            .send_once(method, params, filter)
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Generic call function for async RPC calls, returning only the fields
+    /// of the response listed in `filter`.
+    pub async fn call_with_filter<T: Serialize, U: DeserializeOwned>(
+        &self,
+        method: &str,
+        input: T,
+        filter: Option<Value>,
+    ) -> Result<U, Error> {
+        let params = serde_json::to_value(input)?;
+        let result = self
+            .client
+            .send(method, params, filter)
+            .await?
+            .into_result()?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Start a batch of calls that are sent together to lightningd.
+    pub fn batch(&self) -> Batch<'_, C> {
+        Batch {
+            client: &self.client,
+            request: BatchRequest::new(),
+        }
     }
 
     /// Show information about this node.
@@ -132,6 +271,137 @@ impl LightningRPC {
             .await
     }
 
+    /// List all configuration options, or with [config], just that one, with the source of
+    /// their value and whether they can be changed by setconfig().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listconfigs_v2(
+        &self,
+        config: Option<&str>,
+    ) -> Result<responses::ListConfigsV2, Error> {
+        self.call("listconfigs", requests::ListConfigs { config })
+            .await
+    }
+
+    /// Change the dynamic option {config} to {val} (or set the flag, if no {val}), also in
+    /// the config file unless {transient}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn setconfig(
+        &self,
+        config: &str,
+        val: Option<&str>,
+        transient: Option<bool>,
+    ) -> Result<responses::SetConfig, Error> {
+        self.call(
+            "setconfig",
+            requests::SetConfig {
+                config,
+                val,
+                transient,
+            },
+        )
+        .await
+    }
+
+    /// Start the {plugin} executable, passing it the plugin {options}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn plugin_start(
+        &self,
+        plugin: &str,
+        options: &[(&str, &str)],
+    ) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Start,
+                plugin: Some(plugin),
+                directory: None,
+                options: options.iter().copied().collect(),
+            },
+        )
+        .await
+    }
+
+    /// Stop the dynamic {plugin}, by name or path.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn plugin_stop(&self, plugin: &str) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Stop,
+                plugin: Some(plugin),
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+        .await
+    }
+
+    /// Start all the plugins of {directory}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn plugin_startdir(&self, directory: &str) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Startdir,
+                plugin: None,
+                directory: Some(directory),
+                options: BTreeMap::new(),
+            },
+        )
+        .await
+    }
+
+    /// Start the plugins of the plugin directories that are not running yet.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn plugin_rescan(&self) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Rescan,
+                plugin: None,
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+        .await
+    }
+
+    /// List the running plugins.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn plugin_list(&self) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::List,
+                plugin: None,
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+        .await
+    }
+
     /// Show current peers, if {level} is set, include {log}s.
     #[deprecated(
         since = "0.1.0",
@@ -146,6 +416,94 @@ impl LightningRPC {
             .await
     }
 
+    /// Show the channels with our peers, or only the ones with peer {id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listpeerchannels(
+        &self,
+        id: Option<&str>,
+    ) -> Result<responses::ListPeerChannels, Error> {
+        self.call("listpeerchannels", requests::ListPeerChannels { id })
+            .await
+    }
+
+    /// Show the channels we closed, or only the ones with peer {id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listclosedchannels(
+        &self,
+        id: Option<&str>,
+    ) -> Result<responses::ListClosedChannels, Error> {
+        self.call("listclosedchannels", requests::ListClosedChannels { id })
+            .await
+    }
+
+    /// Show the HTLCs forwarded by this node, see `ListForwardsOptions` for the filters.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listforwards(
+        &self,
+        options: ListForwardsOptions<'_>,
+    ) -> Result<responses::ListForwards, Error> {
+        self.call(
+            "listforwards",
+            requests::ListForwards {
+                status: options.status,
+                in_channel: options.in_channel,
+                out_channel: options.out_channel,
+                pagination: options.pagination,
+            },
+        )
+        .await
+    }
+
+    /// Show the HTLCs of channel {id} (or of all channels, if no {id}), only a page of them
+    /// with {pagination}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listhtlcs(
+        &self,
+        id: Option<&str>,
+        pagination: Option<requests::Pagination>,
+    ) -> Result<responses::ListHtlcs, Error> {
+        self.call("listhtlcs", requests::ListHtlcs { id, pagination })
+            .await
+    }
+
+    /// Change the fees and HTLC limits of channel {id} (a peer id, channel id, short channel
+    /// id or `all`).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn setchannel(
+        &self,
+        id: &str,
+        options: SetChannelOptions,
+    ) -> Result<responses::SetChannel, Error> {
+        self.call(
+            "setchannel",
+            requests::SetChannel {
+                id,
+                feebase: options.feebase,
+                feeppm: options.feeppm,
+                htlcmin: options.htlcmin,
+                htlcmax: options.htlcmax,
+                enforcedelay: options.enforcedelay,
+                ignorefeelimits: options.ignorefeelimits,
+            },
+        )
+        .await
+    }
+
     /// Show invoice {label} (or all, if no {label)).
     #[deprecated(
         since = "0.1.0",
@@ -165,6 +523,34 @@ impl LightningRPC {
                 invstring,
                 payment_hash,
                 offer_id,
+                pagination: None,
+            },
+        )
+        .await
+    }
+
+    /// Show a page of the invoices, selected by {pagination} among invoice {label}
+    /// (or all, if no {label}).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listinvoices_paginated(
+        &self,
+        label: Option<&str>,
+        invstring: Option<&str>,
+        payment_hash: Option<&str>,
+        offer_id: Option<&str>,
+        pagination: requests::Pagination,
+    ) -> Result<responses::ListInvoices, Error> {
+        self.call(
+            "listinvoices",
+            requests::ListInvoices {
+                label,
+                invstring,
+                payment_hash,
+                offer_id,
+                pagination: Some(pagination),
             },
         )
         .await
@@ -332,7 +718,7 @@ impl LightningRPC {
         bolt11: &str,
         options: PayOptions<'_>,
     ) -> Result<responses::Pay, Error> {
-        self.call(
+        self.call_once(
             "pay",
             requests::Pay {
                 bolt11,
@@ -348,6 +734,61 @@ impl LightningRPC {
         .await
     }
 
+    /// Send {amount_msat} to {destination} without an invoice, the preimage
+    /// is generated by us and sent along with the payment.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn keysend(
+        &self,
+        destination: &str,
+        amount_msat: u64,
+        options: KeysendOptions<'_>,
+    ) -> Result<responses::Keysend, Error> {
+        self.call_once(
+            "keysend",
+            requests::Keysend {
+                destination,
+                amount_msat,
+                label: options.label,
+                maxfeepercent: options.maxfeepercent,
+                retry_for: options.retry_for,
+                maxdelay: options.maxdelay,
+                exemptfee: options.exemptfee,
+                maxfee: options.maxfee,
+                routehints: options.routehints,
+                extratlvs: options.extratlvs,
+            },
+        )
+        .await
+    }
+
+    /// Pay the bolt11 or bolt12 invoice {invstring} using the xpay payment engine.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn xpay(
+        &self,
+        invstring: &str,
+        options: XPayOptions<'_>,
+    ) -> Result<responses::XPay, Error> {
+        self.call_once(
+            "xpay",
+            requests::XPay {
+                invstring,
+                amount_msat: options.amount_msat,
+                maxfee: options.maxfee,
+                layers: options.layers,
+                retry_for: options.retry_for,
+                partial_msat: options.partial_msat,
+                maxdelay: options.maxdelay,
+            },
+        )
+        .await
+    }
+
     /// Send along {route} in return for preimage of {payment_hash}, with optional {description}.
     #[deprecated(
         since = "0.1.0",
@@ -360,7 +801,7 @@ impl LightningRPC {
         description: Option<&str>,
         msatoshi: Option<u64>,
     ) -> Result<responses::SendPay, Error> {
-        self.call(
+        self.call_once(
             "sendpay",
             requests::SendPay {
                 route,
@@ -407,66 +848,156 @@ impl LightningRPC {
             requests::ListSendPays {
                 bolt11,
                 payment_hash,
+                pagination: None,
             },
         )
         .await
     }
 
-    /// Decode {bolt11}, using {description} if necessary.
+    /// Show a page of the outgoing payments, selected by {pagination}.
     #[deprecated(
         since = "0.1.0",
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
-    pub async fn decodepay(
+    pub async fn listsendpays_paginated(
         &self,
-        bolt11: &str,
-        description: Option<&str>,
-    ) -> Result<responses::DecodePay, Error> {
+        bolt11: Option<&str>,
+        payment_hash: Option<&str>,
+        pagination: requests::Pagination,
+    ) -> Result<responses::ListSendPays, Error> {
         self.call(
-            "decodepay",
-            requests::DecodePay {
+            "listsendpays",
+            requests::ListSendPays {
                 bolt11,
-                description,
+                payment_hash,
+                pagination: Some(pagination),
             },
         )
         .await
     }
 
-    /// Show route to {id} for {msatoshi}, using {riskfactor} and optional {cltv} (default 9). If
-    /// specified search from {fromid} otherwise use this node as source. Randomize the route with
-    /// up to {fuzzpercent} (0.0 -> 100.0, default 5.0) using {seed} as an arbitrary-size string
-    /// seed.
-    #[allow(clippy::too_many_arguments)]
+    /// Wait until the index {indexname} of {subsystem} reaches {nextvalue}, returning
+    /// the change that made it reach it.
     #[deprecated(
         since = "0.1.0",
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
-    pub async fn getroute(
+    pub async fn wait(
         &self,
-        id: &str,
-        msatoshi: u64,
-        riskfactor: f64,
-        cltv: Option<u64>,
-        fromid: Option<&str>,
-        fuzzpercent: Option<f64>,
-        seed: Option<&str>,
-    ) -> Result<responses::GetRoute, Error> {
+        subsystem: WaitSubsystem,
+        indexname: WaitIndex,
+        nextvalue: u64,
+    ) -> Result<responses::Wait, Error> {
         self.call(
-            "getroute",
-            requests::GetRoute {
-                id,
-                msatoshi,
-                riskfactor,
-                cltv,
-                fromid,
-                fuzzpercent,
-                seed,
+            "wait",
+            requests::Wait {
+                subsystem,
+                indexname,
+                nextvalue,
             },
         )
         .await
     }
 
-    /// Connect to {id} at {host} (which can end in ':port' if not default). {id} can also be of
+    /// Receive the changes of {subsystem} one after the other, starting from the change
+    /// that makes the index {indexname} reach {nextvalue}, e.g. one more than the
+    /// `created_index` of the last entry already processed.
+    ///
+    /// Replaces polling the `list` commands:
+    ///
+    /// ```no_run
+    /// # async fn watch(rpc: clightningrpc::r#async::LightningRPC) {
+    /// use clightningrpc::types::{WaitIndex, WaitSubsystem};
+    ///
+    /// let mut changes = rpc.changes(WaitSubsystem::Invoices, WaitIndex::Updated, 1);
+    /// while let Ok(change) = changes.next().await {
+    ///     println!("invoice changed: {:?}", change.invoices);
+    /// }
+    /// # }
+    /// ```
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn changes(
+        &self,
+        subsystem: WaitSubsystem,
+        indexname: WaitIndex,
+        nextvalue: u64,
+    ) -> Changes<'_, C> {
+        Changes {
+            rpc: self,
+            subsystem,
+            indexname,
+            nextvalue,
+        }
+    }
+
+    /// Decode {bolt11}, using {description} if necessary.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn decodepay(
+        &self,
+        bolt11: &str,
+        description: Option<&str>,
+    ) -> Result<responses::DecodePay, Error> {
+        self.call(
+            "decodepay",
+            requests::DecodePay {
+                bolt11,
+                description,
+            },
+        )
+        .await
+    }
+
+    /// Decode {string}, which can be a bolt11 or bolt12 string, a rune or an
+    /// emergency-recover blob.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn decode(&self, string: &str) -> Result<responses::Decoded, Error> {
+        self.call("decode", requests::Decode { string }).await
+    }
+
+    /// Show route to {id} for {msatoshi}, using {riskfactor} and optional {cltv} (default 9). If
+    /// specified search from {fromid} otherwise use this node as source. Randomize the route with
+    /// up to {fuzzpercent} (0.0 -> 100.0, default 5.0) using {seed} as an arbitrary-size string
+    /// seed.
+    #[allow(clippy::too_many_arguments)]
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn getroute(
+        &self,
+        id: &str,
+        msatoshi: u64,
+        riskfactor: f64,
+        cltv: Option<u64>,
+        fromid: Option<&str>,
+        fuzzpercent: Option<f64>,
+        seed: Option<&str>,
+    ) -> Result<responses::GetRoute, Error> {
+        self.call(
+            "getroute",
+            requests::GetRoute {
+                id,
+                msatoshi,
+                riskfactor,
+                cltv,
+                fromid,
+                fuzzpercent,
+                seed,
+            },
+        )
+        .await
+    }
+
+    /// Connect to {id} at {host} (which can end in ':port' if not default). {id} can also be of
     /// the form id@host.
     #[deprecated(
         since = "0.1.0",
@@ -491,7 +1022,7 @@ impl LightningRPC {
     ///
     /// * `id` - Id of node to fund a channel to
     /// * `amount` - either `AmountOrAll::Amount(n)` for a given amount in satoshi units, or
-    /// `AmountOrAll::All` to spend all available funds
+    ///   `AmountOrAll::All` to spend all available funds
     /// * `feerate` - optional feerate to use for Bitcoin transaction
     #[deprecated(
         since = "0.1.0",
@@ -503,7 +1034,7 @@ impl LightningRPC {
         amount: requests::AmountOrAll,
         feerate: Option<u64>,
     ) -> Result<responses::FundChannel, Error> {
-        self.call(
+        self.call_once(
             "fundchannel",
             requests::FundChannel {
                 id,
@@ -527,7 +1058,7 @@ impl LightningRPC {
         force: Option<bool>,
         timeout: Option<u64>,
     ) -> Result<responses::Close, Error> {
-        self.call("close", requests::Close { id, force, timeout })
+        self.call_once("close", requests::Close { id, force, timeout })
             .await
     }
 
@@ -561,7 +1092,7 @@ impl LightningRPC {
     ///
     /// * `destination` - Bitcoin address to send to
     /// * `amount` - either `AmountOrAll::Amount(n)` for a given amount in satoshi units, or
-    /// `AmountOrAll::All` to spend all available funds
+    ///   `AmountOrAll::All` to spend all available funds
     /// * `feerate` - optional feerate to use for Bitcoin transaction
     #[deprecated(
         since = "0.1.0",
@@ -574,7 +1105,7 @@ impl LightningRPC {
         feerate: Option<u64>,
         minconf: Option<u32>,
     ) -> Result<responses::Withdraw, Error> {
-        self.call(
+        self.call_once(
             "withdraw",
             requests::Withdraw {
                 destination,
@@ -602,6 +1133,1514 @@ impl LightningRPC {
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
     pub async fn stop(&self) -> Result<responses::Stop, Error> {
-        self.call("stop", requests::Stop {}).await
+        self.call_once("stop", requests::Stop {}).await
+    }
+
+    /// Create a BOLT12 offer for {amount_msat} (or any amount, if no {amount_msat})
+    /// with {description}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn offer(
+        &self,
+        amount_msat: Option<u64>,
+        description: Option<&str>,
+        options: OfferOptions<'_>,
+    ) -> Result<responses::Offer, Error> {
+        self.call(
+            "offer",
+            requests::Offer {
+                amount: amount_msat
+                    .map_or(requests::AmountOrAny::Any, requests::AmountOrAny::Amount),
+                description,
+                issuer: options.issuer,
+                label: options.label,
+                quantity_max: options.quantity_max,
+                absolute_expiry: options.absolute_expiry,
+                recurrence: options.recurrence,
+                recurrence_base: options.recurrence_base,
+                recurrence_paywindow: options.recurrence_paywindow,
+                recurrence_limit: options.recurrence_limit,
+                single_use: options.single_use,
+            },
+        )
+        .await
+    }
+
+    /// Show offer {offer_id} (or all, if no {offer_id}), only the active ones if {active_only}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listoffers(
+        &self,
+        offer_id: Option<&str>,
+        active_only: Option<bool>,
+    ) -> Result<responses::ListOffers, Error> {
+        self.call(
+            "listoffers",
+            requests::ListOffers {
+                offer_id,
+                active_only,
+            },
+        )
+        .await
+    }
+
+    /// Disable offer {offer_id}, so no further invoices will be given out.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn disableoffer(&self, offer_id: &str) -> Result<responses::DisableOffer, Error> {
+        self.call("disableoffer", requests::DisableOffer { offer_id })
+            .await
+    }
+
+    /// Enable offer {offer_id} again, after it was disabled.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn enableoffer(&self, offer_id: &str) -> Result<responses::EnableOffer, Error> {
+        self.call("enableoffer", requests::EnableOffer { offer_id })
+            .await
+    }
+
+    /// Request an invoice for the BOLT12 {offer} from its issuer.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn fetchinvoice(
+        &self,
+        offer: &str,
+        options: FetchInvoiceOptions<'_>,
+    ) -> Result<responses::FetchInvoice, Error> {
+        self.call(
+            "fetchinvoice",
+            requests::FetchInvoice {
+                offer,
+                amount_msat: options.amount_msat,
+                quantity: options.quantity,
+                recurrence_counter: options.recurrence_counter,
+                recurrence_start: options.recurrence_start,
+                recurrence_label: options.recurrence_label,
+                timeout: options.timeout,
+                payer_note: options.payer_note,
+            },
+        )
+        .await
+    }
+
+    /// Send an invoice with {label} in response to the BOLT12 invoice request {invreq},
+    /// and wait until it is paid or {timeout} seconds (default 90) elapsed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn sendinvoice(
+        &self,
+        invreq: &str,
+        label: &str,
+        amount_msat: Option<u64>,
+        timeout: Option<u64>,
+        quantity: Option<u64>,
+    ) -> Result<responses::SendInvoice, Error> {
+        self.call(
+            "sendinvoice",
+            requests::SendInvoice {
+                invreq,
+                label,
+                amount_msat,
+                timeout,
+                quantity,
+            },
+        )
+        .await
+    }
+
+    /// Create a BOLT12 invoice request to send {amount_msat} with {description},
+    /// which the recipient answers with an invoice (a refund or an ATM withdrawal).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn invoicerequest(
+        &self,
+        amount_msat: u64,
+        description: &str,
+        options: InvoiceRequestOptions<'_>,
+    ) -> Result<responses::InvoiceRequest, Error> {
+        self.call(
+            "invoicerequest",
+            requests::InvoiceRequest {
+                amount: amount_msat,
+                description,
+                issuer: options.issuer,
+                label: options.label,
+                absolute_expiry: options.absolute_expiry,
+                single_use: options.single_use,
+            },
+        )
+        .await
+    }
+
+    /// Show invoice request {invreq_id} (or all, if no {invreq_id}), only the active
+    /// ones if {active_only}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listinvoicerequests(
+        &self,
+        invreq_id: Option<&str>,
+        active_only: Option<bool>,
+    ) -> Result<responses::ListInvoiceRequests, Error> {
+        self.call(
+            "listinvoicerequests",
+            requests::ListInvoiceRequests {
+                invreq_id,
+                active_only,
+            },
+        )
+        .await
+    }
+
+    /// Disable invoice request {invreq_id}, so no further invoices will be accepted.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn disableinvoicerequest(
+        &self,
+        invreq_id: &str,
+    ) -> Result<responses::DisableInvoiceRequest, Error> {
+        self.call(
+            "disableinvoicerequest",
+            requests::DisableInvoiceRequest { invreq_id },
+        )
+        .await
+    }
+
+    /// Create a PSBT spending {satoshi} (or all) of the wallet funds at {feerate}, for a
+    /// transaction with {startweight} weight before the inputs are added.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn fundpsbt(
+        &self,
+        satoshi: requests::AmountOrAll,
+        feerate: &str,
+        startweight: u32,
+        options: FundPsbtOptions,
+    ) -> Result<responses::FundPsbt, Error> {
+        self.call_once(
+            "fundpsbt",
+            requests::FundPsbt {
+                satoshi,
+                feerate,
+                startweight,
+                minconf: options.minconf,
+                reserve: options.reserve,
+                locktime: options.locktime,
+                min_witness_weight: options.min_witness_weight,
+                excess_as_change: options.excess_as_change,
+                nonwrapped: options.nonwrapped,
+                opening_anchor_channel: options.opening_anchor_channel,
+            },
+        )
+        .await
+    }
+
+    /// Create a PSBT spending {satoshi} (or all) from the given {utxos} (`txid:vout`) at
+    /// {feerate}, for a transaction with {startweight} weight before the inputs are added.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn utxopsbt(
+        &self,
+        satoshi: requests::AmountOrAll,
+        feerate: &str,
+        startweight: u32,
+        utxos: &[&str],
+        options: UtxoPsbtOptions,
+    ) -> Result<responses::UtxoPsbt, Error> {
+        self.call_once(
+            "utxopsbt",
+            requests::UtxoPsbt {
+                satoshi,
+                feerate,
+                startweight,
+                utxos,
+                reserve: options.reserve,
+                reservedok: options.reservedok,
+                locktime: options.locktime,
+                min_witness_weight: options.min_witness_weight,
+                excess_as_change: options.excess_as_change,
+                opening_anchor_channel: options.opening_anchor_channel,
+            },
+        )
+        .await
+    }
+
+    /// Reserve the inputs of {psbt} for {reserve} blocks (default 72), failing if any is
+    /// already reserved and {exclusive} (default true).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn reserveinputs(
+        &self,
+        psbt: &Psbt,
+        exclusive: Option<bool>,
+        reserve: Option<u32>,
+    ) -> Result<responses::ReserveInputs, Error> {
+        self.call_once(
+            "reserveinputs",
+            requests::ReserveInputs {
+                psbt,
+                exclusive,
+                reserve,
+            },
+        )
+        .await
+    }
+
+    /// Release the reservation of the inputs of {psbt} by {reserve} blocks (default 72).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn unreserveinputs(
+        &self,
+        psbt: &Psbt,
+        reserve: Option<u32>,
+    ) -> Result<responses::UnreserveInputs, Error> {
+        self.call_once(
+            "unreserveinputs",
+            requests::UnreserveInputs { psbt, reserve },
+        )
+        .await
+    }
+
+    /// Sign the wallet inputs of {psbt}, only the inputs at the indexes {signonly} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn signpsbt(
+        &self,
+        psbt: &Psbt,
+        signonly: Option<&[u32]>,
+    ) -> Result<responses::SignPsbt, Error> {
+        self.call("signpsbt", requests::SignPsbt { psbt, signonly })
+            .await
+    }
+
+    /// Finalize, extract and broadcast the fully signed {psbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn sendpsbt(
+        &self,
+        psbt: &Psbt,
+        reserve: Option<u32>,
+    ) -> Result<responses::SendPsbt, Error> {
+        self.call_once("sendpsbt", requests::SendPsbt { psbt, reserve })
+            .await
+    }
+
+    /// Prepare a transaction paying the {outputs}, reserving the used wallet outputs
+    /// until it is sent with txsend() or discarded with txdiscard().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn txprepare(
+        &self,
+        outputs: &[requests::TxOutput<'_>],
+        feerate: Option<&str>,
+        minconf: Option<u32>,
+        utxos: Option<&[&str]>,
+    ) -> Result<responses::TxPrepare, Error> {
+        self.call_once(
+            "txprepare",
+            requests::TxPrepare {
+                outputs,
+                feerate,
+                minconf,
+                utxos,
+            },
+        )
+        .await
+    }
+
+    /// Sign and broadcast the transaction {txid} created by txprepare().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn txsend(&self, txid: &str) -> Result<responses::TxSend, Error> {
+        self.call_once("txsend", requests::TxSend { txid }).await
+    }
+
+    /// Abandon the transaction {txid} created by txprepare(), releasing its inputs.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn txdiscard(&self, txid: &str) -> Result<responses::TxDiscard, Error> {
+        self.call("txdiscard", requests::TxDiscard { txid }).await
+    }
+
+    /// Show the transactions tracked by the wallet.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listtransactions(&self) -> Result<responses::ListTransactions, Error> {
+        self.call("listtransactions", requests::ListTransactions {})
+            .await
+    }
+
+    /// Show the addresses issued by the wallet, only {address} if given, {limit} of them
+    /// starting from the key index {start} otherwise.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listaddresses(
+        &self,
+        address: Option<&str>,
+        start: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<responses::ListAddresses, Error> {
+        self.call(
+            "listaddresses",
+            requests::ListAddresses {
+                address,
+                start,
+                limit,
+            },
+        )
+        .await
+    }
+
+    /// Send the wallet funds to several {outputs} in a single transaction.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn multiwithdraw(
+        &self,
+        outputs: &[requests::TxOutput<'_>],
+        feerate: Option<&str>,
+        minconf: Option<u32>,
+        utxos: Option<&[&str]>,
+    ) -> Result<responses::MultiWithdraw, Error> {
+        self.call_once(
+            "multiwithdraw",
+            requests::MultiWithdraw {
+                outputs,
+                feerate,
+                minconf,
+                utxos,
+            },
+        )
+        .await
+    }
+
+    /// Open channels with all the {destinations} in a single funding transaction.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn multifundchannel(
+        &self,
+        destinations: &[requests::MultiFundDestination<'_>],
+        options: MultiFundChannelOptions<'_>,
+    ) -> Result<responses::MultiFundChannel, Error> {
+        self.call_once(
+            "multifundchannel",
+            requests::MultiFundChannel {
+                destinations,
+                feerate: options.feerate,
+                minconf: options.minconf,
+                utxos: options.utxos.as_deref(),
+                minchannels: options.minchannels,
+                commitment_feerate: options.commitment_feerate,
+            },
+        )
+        .await
+    }
+
+    /// Start opening a channel of {amount} satoshi with {id}, returning the address the
+    /// funding transaction must pay to.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn fundchannel_start(
+        &self,
+        id: &str,
+        amount: u64,
+        options: FundChannelStartOptions<'_>,
+    ) -> Result<responses::FundChannelStart, Error> {
+        self.call_once(
+            "fundchannel_start",
+            requests::FundChannelStart {
+                id,
+                amount,
+                feerate: options.feerate,
+                announce: options.announce,
+                close_to: options.close_to,
+                push_msat: options.push_msat,
+                mindepth: options.mindepth,
+                reserve: options.reserve,
+                channel_type: options.channel_type.as_deref(),
+            },
+        )
+        .await
+    }
+
+    /// Complete the channel opening with {id} started by fundchannel_start(), with the
+    /// funding transaction {psbt}, which can be broadcast once the commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn fundchannel_complete(
+        &self,
+        id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::FundChannelComplete, Error> {
+        self.call_once(
+            "fundchannel_complete",
+            requests::FundChannelComplete { id, psbt },
+        )
+        .await
+    }
+
+    /// Cancel the channel opening with {id} started by fundchannel_start().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn fundchannel_cancel(
+        &self,
+        id: &str,
+    ) -> Result<responses::FundChannelCancel, Error> {
+        self.call("fundchannel_cancel", requests::FundChannelCancel { id })
+            .await
+    }
+
+    /// Start opening a dual-funded channel with {id}, contributing {amount} satoshi from
+    /// the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn openchannel_init(
+        &self,
+        id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        options: OpenChannelOptions<'_>,
+    ) -> Result<responses::OpenChannelInit, Error> {
+        self.call_once(
+            "openchannel_init",
+            requests::OpenChannelInit {
+                id,
+                amount,
+                initialpsbt,
+                commitment_feerate: options.commitment_feerate,
+                funding_feerate: options.funding_feerate,
+                announce: options.announce,
+                close_to: options.close_to,
+                request_amt: options.request_amt,
+                compact_lease: options.compact_lease,
+                channel_type: options.channel_type.as_deref(),
+            },
+        )
+        .await
+    }
+
+    /// Exchange the updated {psbt} of the dual-funded opening of {channel_id} with the peer,
+    /// until the commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn openchannel_update(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::OpenChannelUpdate, Error> {
+        self.call_once(
+            "openchannel_update",
+            requests::OpenChannelUpdate { channel_id, psbt },
+        )
+        .await
+    }
+
+    /// Send the {signed_psbt} of the dual-funded opening of {channel_id} to the peer, the
+    /// funding transaction is broadcast once both sides signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn openchannel_signed(
+        &self,
+        channel_id: &str,
+        signed_psbt: &Psbt,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        self.call_once(
+            "openchannel_signed",
+            requests::OpenChannelSigned {
+                channel_id,
+                signed_psbt,
+            },
+        )
+        .await
+    }
+
+    /// Replace the funding transaction of the pending dual-funded channel {channel_id}
+    /// (RBF), contributing {amount} satoshi from the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn openchannel_bump(
+        &self,
+        channel_id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        funding_feerate: Option<&str>,
+    ) -> Result<responses::OpenChannelBump, Error> {
+        self.call_once(
+            "openchannel_bump",
+            requests::OpenChannelBump {
+                channel_id,
+                amount,
+                initialpsbt,
+                funding_feerate,
+            },
+        )
+        .await
+    }
+
+    /// Abort the dual-funded opening of {channel_id}, before the funding is signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn openchannel_abort(
+        &self,
+        channel_id: &str,
+    ) -> Result<responses::OpenChannelAbort, Error> {
+        self.call(
+            "openchannel_abort",
+            requests::OpenChannelAbort { channel_id },
+        )
+        .await
+    }
+
+    /// Start splicing {relative_amount} satoshi into (positive) or out of (negative)
+    /// {channel_id}, funding a splice-in from the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn splice_init(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+        force_feerate: Option<bool>,
+    ) -> Result<responses::SpliceInit, Error> {
+        self.call_once(
+            "splice_init",
+            requests::SpliceInit {
+                channel_id,
+                relative_amount,
+                initialpsbt,
+                feerate_per_kw,
+                force_feerate,
+            },
+        )
+        .await
+    }
+
+    /// Exchange the updated {psbt} of the splice of {channel_id} with the peer, until the
+    /// commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn splice_update(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::SpliceUpdate, Error> {
+        self.call_once("splice_update", requests::SpliceUpdate { channel_id, psbt })
+            .await
+    }
+
+    /// Send the signed {psbt} of the splice of {channel_id} to the peer, the splice
+    /// transaction is broadcast once both sides signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn splice_signed(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+        sign_first: Option<bool>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        self.call_once(
+            "splice_signed",
+            requests::SpliceSigned {
+                channel_id,
+                psbt,
+                sign_first,
+            },
+        )
+        .await
+    }
+
+    /// Open a dual-funded channel with {id}, contributing {amount} satoshi from the inputs
+    /// of {initialpsbt} (e.g. made by fundpsbt()).
+    ///
+    /// The PSBT is exchanged with openchannel_update() until the commitments are secured,
+    /// then its wallet inputs are signed and the funding transaction is broadcast. If a step
+    /// fails, or the commitments are not secured after `MAX_UPDATE_ROUNDS` updates
+    /// (`Error::CommitmentsNotSecured`), the opening is aborted and the inputs of
+    /// {initialpsbt} are unreserved.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    #[allow(deprecated)]
+    pub async fn open_dual_funded_channel(
+        &self,
+        id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        options: OpenChannelOptions<'_>,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        let result = match self
+            .openchannel_init(id, amount, initialpsbt, options)
+            .await
+        {
+            Ok(init) => {
+                let result = self
+                    .finish_openchannel(&init.channel_id, init.psbt, init.commitments_secured)
+                    .await;
+                if result.is_err() {
+                    // best effort, the opening times out on the peer side anyway
+                    let _ = self.openchannel_abort(&init.channel_id).await;
+                }
+                result
+            }
+            Err(err) => Err(err),
+        };
+        if result.is_err() {
+            // best effort, the reservation expires after 72 blocks anyway
+            let _ = self.unreserveinputs(initialpsbt, None).await;
+        }
+        result
+    }
+
+    #[allow(deprecated)]
+    async fn finish_openchannel(
+        &self,
+        channel_id: &str,
+        mut psbt: Psbt,
+        mut commitments_secured: bool,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        let mut rounds = 0;
+        while !commitments_secured {
+            if rounds == MAX_UPDATE_ROUNDS {
+                return Err(Error::CommitmentsNotSecured);
+            }
+            rounds += 1;
+            let update = self.openchannel_update(channel_id, &psbt).await?;
+            psbt = update.psbt;
+            commitments_secured = update.commitments_secured;
+        }
+        let signed = self.signpsbt(&psbt, None).await?;
+        self.openchannel_signed(channel_id, &signed.signed_psbt)
+            .await
+    }
+
+    /// Splice {relative_amount} satoshi into (positive) or out of (negative) {channel_id},
+    /// funding a splice-in from the inputs of {initialpsbt} (e.g. made by fundpsbt()).
+    ///
+    /// The PSBT is exchanged with splice_update() until the commitments are secured, then
+    /// the wallet inputs of {initialpsbt} are signed and the splice transaction is broadcast.
+    /// If a step fails, or the commitments are not secured after `MAX_UPDATE_ROUNDS` updates
+    /// (`Error::CommitmentsNotSecured`), the inputs of {initialpsbt} are unreserved.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    #[allow(deprecated)]
+    pub async fn splice_channel(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        let result = self
+            .finish_splice(channel_id, relative_amount, initialpsbt, feerate_per_kw)
+            .await;
+        if let (Err(_), Some(initialpsbt)) = (&result, initialpsbt) {
+            // best effort, the reservation expires after 72 blocks anyway
+            let _ = self.unreserveinputs(initialpsbt, None).await;
+        }
+        result
+    }
+
+    #[allow(deprecated)]
+    async fn finish_splice(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        let init = self
+            .splice_init(
+                channel_id,
+                relative_amount,
+                initialpsbt,
+                feerate_per_kw,
+                None,
+            )
+            .await?;
+        let mut psbt = init.psbt;
+        for _ in 0..MAX_UPDATE_ROUNDS {
+            let update = self.splice_update(channel_id, &psbt).await?;
+            psbt = update.psbt;
+            if update.commitments_secured {
+                if initialpsbt.is_some() {
+                    psbt = self.signpsbt(&psbt, None).await?.signed_psbt;
+                }
+                return self.splice_signed(channel_id, &psbt, None).await;
+            }
+        }
+        Err(Error::CommitmentsNotSecured)
+    }
+
+    /// Send the custom {message} to the connected peer {node_id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn sendcustommsg(
+        &self,
+        node_id: &str,
+        message: &CustomMessage,
+    ) -> Result<responses::SendCustomMsg, Error> {
+        self.call_once(
+            "sendcustommsg",
+            requests::SendCustomMsg {
+                node_id,
+                msg: message.to_hex(),
+            },
+        )
+        .await
+    }
+
+    /// Send an onion message along the blinded path {hops}, through the connected peer
+    /// {first_id}, with the blinding point {path_key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn sendonionmessage(
+        &self,
+        first_id: &str,
+        path_key: &str,
+        hops: &[requests::OnionMessageHop<'_>],
+    ) -> Result<responses::SendOnionMessage, Error> {
+        self.call_once(
+            "sendonionmessage",
+            requests::SendOnionMessage {
+                first_id,
+                path_key,
+                hops,
+            },
+        )
+        .await
+    }
+
+    /// Process the onion {message} as if it was received from a peer, with the blinding
+    /// point {path_key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn injectonionmessage(
+        &self,
+        path_key: &str,
+        message: &Hex,
+    ) -> Result<responses::InjectOnionMessage, Error> {
+        self.call_once(
+            "injectonionmessage",
+            requests::InjectOnionMessage { path_key, message },
+        )
+        .await
+    }
+
+    /// Sign {message} with the node key, to prove the node ownership.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn signmessage(&self, message: &str) -> Result<responses::SignMessage, Error> {
+        self.call("signmessage", requests::SignMessage { message })
+            .await
+    }
+
+    /// Check the {zbase} signature of {message}, made by {pubkey} if given. Without {pubkey},
+    /// the signature is only verified if it was made by a node known from the gossip.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn checkmessage(
+        &self,
+        message: &str,
+        zbase: &str,
+        pubkey: Option<&str>,
+    ) -> Result<responses::CheckMessage, Error> {
+        self.call(
+            "checkmessage",
+            requests::CheckMessage {
+                message,
+                zbase,
+                pubkey,
+            },
+        )
+        .await
+    }
+
+    /// Sign the BOLT11 {invstring} with the node key.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn signinvoice(&self, invstring: &str) -> Result<responses::SignInvoice, Error> {
+        self.call("signinvoice", requests::SignInvoice { invstring })
+            .await
+    }
+
+    /// Derive a secret from the node secret and either {hex} or {string}, which must be unique
+    /// to the service using it.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn makesecret(
+        &self,
+        hex: Option<&str>,
+        string: Option<&str>,
+    ) -> Result<responses::MakeSecret, Error> {
+        self.call("makesecret", requests::MakeSecret { hex, string })
+            .await
+    }
+
+    /// Return the encrypted emergency.recover data, to back up the channels of the node.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn getemergencyrecoverdata(
+        &self,
+    ) -> Result<responses::GetEmergencyRecoverData, Error> {
+        self.call(
+            "getemergencyrecoverdata",
+            requests::GetEmergencyRecoverData {},
+        )
+        .await
+    }
+
+    /// Recover the channels from the emergency.recover file, asking the peers to close them.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn emergencyrecover(&self) -> Result<responses::EmergencyRecover, Error> {
+        self.call("emergencyrecover", requests::EmergencyRecover {})
+            .await
+    }
+
+    /// Recover the channels from the static channel backups {scb}, asking the peers to close
+    /// them.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn recoverchannel(&self, scb: &[Hex]) -> Result<responses::RecoverChannel, Error> {
+        self.call("recoverchannel", requests::RecoverChannel { scb })
+            .await
+    }
+
+    /// Return the static channel backups of the channels, see recoverchannel().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn staticbackup(&self) -> Result<responses::StaticBackup, Error> {
+        self.call("staticbackup", requests::StaticBackup {}).await
+    }
+
+    /// Create a new rune with the {restrictions} (see `crate::rune::Restriction`), or
+    /// add them to the existing {rune}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn createrune(
+        &self,
+        rune: Option<&str>,
+        restrictions: Option<&[Restriction]>,
+    ) -> Result<responses::CreateRune, Error> {
+        self.call(
+            "createrune",
+            requests::CreateRune {
+                rune,
+                restrictions: restrictions
+                    .map(|restrictions| restrictions.iter().map(Restriction::to_strings).collect()),
+            },
+        )
+        .await
+    }
+
+    /// Show {rune} decoded, or all the runes created by this node if no {rune}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn showrunes(&self, rune: Option<&str>) -> Result<responses::ShowRunes, Error> {
+        self.call("showrunes", requests::ShowRunes { rune }).await
+    }
+
+    /// Blacklist the runes with unique ids from {start} to {end} (default {start}), or
+    /// remove them from the blacklist if {relist}. Only shows the blacklist without {start}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn blacklistrune(
+        &self,
+        start: Option<u64>,
+        end: Option<u64>,
+        relist: Option<bool>,
+    ) -> Result<responses::BlacklistRune, Error> {
+        self.call(
+            "blacklistrune",
+            requests::BlacklistRune { start, end, relist },
+        )
+        .await
+    }
+
+    /// Check whether {rune} allows the node {nodeid} to run {method} with {params}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn checkrune(
+        &self,
+        rune: &str,
+        nodeid: Option<&str>,
+        method: Option<&str>,
+        params: Option<&Value>,
+    ) -> Result<responses::CheckRune, Error> {
+        self.call(
+            "checkrune",
+            requests::CheckRune {
+                rune,
+                nodeid,
+                method,
+                params,
+            },
+        )
+        .await
+    }
+
+    /// Store {data} under the hierarchical {key}, according to {mode} (default must-create).
+    /// With {generation}, the entry is only changed if it is still at that generation.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn datastore<K: AsRef<str>>(
+        &self,
+        key: &[K],
+        data: requests::DatastoreData<'_>,
+        mode: Option<requests::DatastoreMode>,
+        generation: Option<u64>,
+    ) -> Result<responses::Datastore, Error> {
+        let (string, hex) = match data {
+            requests::DatastoreData::String(string) => (Some(string), None),
+            requests::DatastoreData::Hex(hex) => (None, Some(hex)),
+        };
+        self.call_once(
+            "datastore",
+            requests::Datastore {
+                key: key.iter().map(AsRef::as_ref).collect(),
+                string,
+                hex,
+                mode,
+                generation,
+            },
+        )
+        .await
+    }
+
+    /// Remove the entry {key} from the datastore, only if it is still at {generation} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn deldatastore<K: AsRef<str>>(
+        &self,
+        key: &[K],
+        generation: Option<u64>,
+    ) -> Result<responses::DelDatastore, Error> {
+        self.call(
+            "deldatastore",
+            requests::DelDatastore {
+                key: key.iter().map(AsRef::as_ref).collect(),
+                generation,
+            },
+        )
+        .await
+    }
+
+    /// Show the datastore entry {key} and its children, or the top-level entries if no {key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn listdatastore<K: AsRef<str>>(
+        &self,
+        key: Option<&[K]>,
+    ) -> Result<responses::ListDatastore, Error> {
+        self.call(
+            "listdatastore",
+            requests::ListDatastore {
+                key: key.map(|key| key.iter().map(AsRef::as_ref).collect()),
+            },
+        )
+        .await
+    }
+
+    /// Show the bytes used by the datastore entry {key} and its children, or by the whole
+    /// datastore if no {key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn datastoreusage<K: AsRef<str>>(
+        &self,
+        key: Option<&[K]>,
+    ) -> Result<responses::DatastoreUsage, Error> {
+        self.call(
+            "datastoreusage",
+            requests::DatastoreUsage {
+                key: key.map(|key| key.iter().map(AsRef::as_ref).collect()),
+            },
+        )
+        .await
+    }
+
+    /// Find routes paying {amount_msat} from {source} to {destination}, possibly split in
+    /// several parts, using the gossip amended by the askrene {layers}. The fees of all
+    /// the parts together stay below {maxfee_msat}, and {final_cltv} is the delay
+    /// required by the destination.
+    #[allow(clippy::too_many_arguments)]
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn getroutes(
+        &self,
+        source: &str,
+        destination: &str,
+        amount_msat: u64,
+        layers: &[&str],
+        maxfee_msat: u64,
+        final_cltv: u32,
+        options: GetRoutesOptions,
+    ) -> Result<responses::GetRoutes, Error> {
+        self.call(
+            "getroutes",
+            requests::GetRoutes {
+                source,
+                destination,
+                amount_msat,
+                layers,
+                maxfee_msat,
+                final_cltv,
+                maxdelay: options.maxdelay,
+                maxparts: options.maxparts,
+            },
+        )
+        .await
+    }
+
+    /// Create the askrene layer {layer}, kept across restarts if {persistent}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_create_layer(
+        &self,
+        layer: &str,
+        persistent: Option<bool>,
+    ) -> Result<responses::AskreneCreateLayer, Error> {
+        self.call(
+            "askrene-create-layer",
+            requests::AskreneCreateLayer { layer, persistent },
+        )
+        .await
+    }
+
+    /// Record in {layer} what was learnt about the channel direction {short_channel_id_dir}
+    /// (e.g. '103x1x0/1') while sending {amount_msat} through it, {inform} telling
+    /// whether the payment failed (constrained) or went through it.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_inform_channel(
+        &self,
+        layer: &str,
+        short_channel_id_dir: &str,
+        amount_msat: Option<u64>,
+        inform: Option<AskreneInform>,
+    ) -> Result<responses::AskreneInformChannel, Error> {
+        self.call(
+            "askrene-inform-channel",
+            requests::AskreneInformChannel {
+                layer,
+                short_channel_id_dir,
+                amount_msat,
+                inform,
+            },
+        )
+        .await
+    }
+
+    /// Override in {layer} the gossip of the channel direction {short_channel_id_dir},
+    /// see `AskreneUpdateChannelOptions` for the fields.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_update_channel(
+        &self,
+        layer: &str,
+        short_channel_id_dir: &str,
+        options: AskreneUpdateChannelOptions,
+    ) -> Result<responses::AskreneUpdateChannel, Error> {
+        self.call(
+            "askrene-update-channel",
+            requests::AskreneUpdateChannel {
+                layer,
+                short_channel_id_dir,
+                enabled: options.enabled,
+                htlc_minimum_msat: options.htlc_minimum_msat,
+                htlc_maximum_msat: options.htlc_maximum_msat,
+                fee_base_msat: options.fee_base_msat,
+                fee_proportional_millionths: options.fee_proportional_millionths,
+                cltv_expiry_delta: options.cltv_expiry_delta,
+            },
+        )
+        .await
+    }
+
+    /// Add to {layer} a channel {short_channel_id} of {capacity_msat} from {source} to
+    /// {destination} which is not in the gossip (e.g. a private channel), its fees must
+    /// then be set with askrene_update_channel().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_create_channel(
+        &self,
+        layer: &str,
+        source: &str,
+        destination: &str,
+        short_channel_id: &str,
+        capacity_msat: u64,
+    ) -> Result<responses::AskreneCreateChannel, Error> {
+        self.call(
+            "askrene-create-channel",
+            requests::AskreneCreateChannel {
+                layer,
+                source,
+                destination,
+                short_channel_id,
+                capacity_msat,
+            },
+        )
+        .await
+    }
+
+    /// Show the askrene layer {layer}, or all the layers if no {layer}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_listlayers(
+        &self,
+        layer: Option<&str>,
+    ) -> Result<responses::AskreneListLayers, Error> {
+        self.call("askrene-listlayers", requests::AskreneListLayers { layer })
+            .await
+    }
+
+    /// Remove from {layer} the channel constraints recorded before the UNIX time {cutoff}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub async fn askrene_age(
+        &self,
+        layer: &str,
+        cutoff: u64,
+    ) -> Result<responses::AskreneAge, Error> {
+        self.call("askrene-age", requests::AskreneAge { layer, cutoff })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use clightningrpc_common::errors::Error;
+    use clightningrpc_common::retry::RetryPolicy;
+    use clightningrpc_common::transport::Mock;
+    use clightningrpc_common::types::Notification;
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    use super::LightningRPC;
+    use crate::responses;
+    use crate::types::{WaitIndex, WaitSubsystem};
+
+    /// Fake lightningd listening on a new socket named after {name}, sending back
+    /// the messages returned by {answer} for each request (e.g. notifications
+    /// followed by the response). Returns the socket path and the requests received.
+    fn fake_lightningd<F>(name: &str, answer: F) -> (PathBuf, Arc<Mutex<Vec<Value>>>)
+    where
+        F: Fn(&Value) -> Vec<Value> + Send + Sync + 'static,
+    {
+        let path = std::env::temp_dir().join(format!("cln4rust-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        let answer = Arc::new(answer);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let received = received.clone();
+                let answer = answer.clone();
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    loop {
+                        let mut messages = Vec::new();
+                        let mut decoded = serde_json::Deserializer::from_slice(&buffer).into_iter();
+                        while let Some(Ok(request)) = decoded.next() {
+                            messages.extend(answer(&request));
+                            received.lock().unwrap().push(request);
+                        }
+                        let offset = decoded.byte_offset();
+                        buffer.drain(..offset);
+                        for message in messages {
+                            let message = format!("{message}\n\n");
+                            stream.write_all(message.as_bytes()).await.unwrap();
+                        }
+                        let mut chunk = [0; 1024];
+                        match stream.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                });
+            }
+        });
+        (path, requests)
+    }
+
+    /// The response to {request} with {result}
+    fn result(request: &Value, result: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    #[tokio::test]
+    async fn batch_over_the_socket() {
+        let (path, requests) = fake_lightningd("async-batch", |request| {
+            let answer = match request["method"].as_str() {
+                Some("listfunds") => json!({ "outputs": [], "channels": [] }),
+                _ => json!({ "channels": [] }),
+            };
+            vec![result(request, answer)]
+        });
+        let lightning = LightningRPC::new(&path);
+
+        let mut batch = lightning.batch();
+        let channels = batch
+            .call::<_, responses::ListChannels>("listchannels", json!({}))
+            .unwrap();
+        let funds = batch
+            .call::<_, responses::ListFunds>("listfunds", json!({}))
+            .unwrap();
+        let mut other = lightning.batch();
+        let info = other.call::<_, Value>("getinfo", json!({})).unwrap();
+        let mut response = batch.send().await.unwrap();
+        assert!(response.take(funds).unwrap().outputs.is_empty());
+        assert!(response.take(channels).unwrap().channels.is_empty());
+        assert!(matches!(response.take(info), Err(Error::BatchMismatch)));
+        let methods: Vec<Value> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request["method"].clone())
+            .collect();
+        assert_eq!(methods, [json!("listchannels"), json!("listfunds")]);
+    }
+
+    #[tokio::test]
+    async fn notifications_of_a_running_command() {
+        let (path, requests) = fake_lightningd("async-notifications", |request| {
+            if request["method"] == "notifications" {
+                return vec![result(request, json!({}))];
+            }
+            vec![
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "message",
+                    "params": { "id": request["id"], "level": "info", "message": "Attempting route" }
+                }),
+                result(request, json!({ "status": "complete" })),
+            ]
+        });
+        let mut lightning = LightningRPC::new(&path);
+        lightning.client().set_persistent(true);
+        let mut notifications = lightning.client().subscribe_notifications();
+
+        let paid: Value = lightning
+            .call("pay", json!({ "bolt11": "lnbcrt1" }))
+            .await
+            .unwrap();
+        assert_eq!(paid["status"], "complete");
+        match notifications.recv().await.unwrap() {
+            Notification::Message(message) => assert_eq!(message.message, "Attempting route"),
+            other => panic!("unexpected notification {:?}", other),
+        }
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["params"], json!({ "enable": true }));
+    }
+
+    #[tokio::test]
+    async fn filter_sent_with_the_request() {
+        let (path, requests) = fake_lightningd("async-filter", |request| {
+            vec![result(request, json!({ "channels": [] }))]
+        });
+        let mut lightning = LightningRPC::new(&path);
+        lightning.set_filter_responses(true);
+        let channels = lightning.listchannels(None, None, None).await.unwrap();
+        assert!(channels.channels.is_empty());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["method"], "listchannels");
+        assert_eq!(
+            requests[0]["filter"]["channels"][0]["short_channel_id"],
+            json!(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_only_read_only_commands() {
+        let attempts = Mutex::new(0);
+        let (path, requests) = fake_lightningd("async-retry", move |request| {
+            let mut attempts = attempts.lock().unwrap();
+            *attempts += 1;
+            if request["method"] == "getinfo" && *attempts > 1 {
+                return vec![result(request, json!({ "id": "02aa" }))];
+            }
+            vec![json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -1, "message": "Still syncing with bitcoin network" }
+            })]
+        });
+        let mut lightning = LightningRPC::new(&path);
+        let mut policy = RetryPolicy::new(3);
+        policy.set_backoff(Duration::from_millis(1), Duration::from_millis(1));
+        lightning.client().set_retry_policy(Some(policy));
+
+        let info: Value = lightning.call("getinfo", json!({})).await.unwrap();
+        assert_eq!(info["id"], "02aa");
+        assert!(lightning
+            .call::<_, Value>("invoice", json!({ "label": "a" }))
+            .await
+            .is_err());
+
+        let methods: Vec<Value> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request["method"].clone())
+            .collect();
+        assert_eq!(
+            methods,
+            [json!("getinfo"), json!("getinfo"), json!("invoice")]
+        );
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn changes_follow_the_index() {
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning.client.expect(
+            "wait",
+            json!({ "subsystem": "invoices", "updated": 4, "invoices": { "status": "paid" } }),
+        );
+        lightning
+            .client
+            .expect("wait", json!({ "subsystem": "invoices", "updated": 5 }));
+
+        let mut changes = lightning.changes(WaitSubsystem::Invoices, WaitIndex::Updated, 2);
+        let change = changes.next().await.unwrap();
+        assert_eq!(change.invoices.unwrap().status.as_deref(), Some("paid"));
+        assert_eq!(changes.nextvalue(), 5);
+        changes.next().await.unwrap();
+        assert!(changes.next().await.is_err());
+
+        let nextvalues: Vec<_> = lightning
+            .client
+            .requests()
+            .iter()
+            .map(|(_, params)| params["nextvalue"].clone())
+            .collect();
+        assert_eq!(nextvalues, [json!(2), json!(5), json!(6)]);
     }
 }
@@ -0,0 +1,317 @@
+//! Bindings for the commands of the bookkeeper plugin, bundled with Core Lightning
+//!
+//! The bookkeeper records every coin movement of the node (on-chain deposits and
+//! withdrawals, payments, forwards, fees...) per account, the wallet or a channel,
+//! and summarizes them as balances, income events or channel APYs.
+//!
+//! The commands are methods of `LightningRPC`, this module is only built with the
+//! `bkpr` feature.
+use serde::{Deserialize, Serialize};
+
+use clightningrpc_common::errors::Error;
+use clightningrpc_common::transport::Transport;
+
+use crate::lightningrpc::LightningRPC;
+
+pub mod requests;
+pub mod responses;
+
+/// Kind of an event in 'bkpr-listaccountevents'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    /// an on-chain output was created or spent
+    Chain,
+    /// a payment or forward changed a channel balance
+    Channel,
+    /// on-chain fees paid by the account
+    OnchainFee,
+    /// an event type added by a newer version of the plugin
+    #[serde(other)]
+    Unknown,
+}
+
+/// Tag of a coin movement, telling why the balance of an account changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinMovementTag {
+    /// funds received on-chain by the wallet
+    Deposit,
+    /// funds sent on-chain by the wallet
+    Withdrawal,
+    /// output claimed with a penalty transaction
+    Penalty,
+    /// payment of an invoice, incoming or outgoing
+    Invoice,
+    /// HTLC forwarded through the channel
+    Routed,
+    /// amount pushed to the peer when opening the channel
+    Pushed,
+    /// funding output of the channel
+    ChannelOpen,
+    /// channel funding output spent by the closing transaction
+    ChannelClose,
+    /// our output of a unilateral close, spendable after a delay
+    DelayedToUs,
+    /// HTLC output claimed after its timeout
+    HtlcTimeout,
+    /// HTLC output claimed with its preimage
+    HtlcFulfill,
+    /// second stage HTLC transaction
+    HtlcTx,
+    /// funds swept back to the wallet
+    ToWallet,
+    /// anchor output
+    Anchor,
+    /// output belonging to the peer
+    ToThem,
+    /// output claimed by the peer with a penalty transaction
+    Penalized,
+    /// output swept by the peer
+    Stolen,
+    /// output lost to the miners, too small to be claimed
+    ToMiner,
+    /// the account opened the channel
+    Opener,
+    /// fee of a liquidity lease
+    LeaseFee,
+    /// the channel is a liquidity lease
+    Leased,
+    /// output which the peer could still sweep
+    Stealable,
+    /// channel negotiated but not yet funded
+    ChannelProposed,
+    /// channel resized by a splice
+    Splice,
+    /// adjustment of a penalty output
+    PenaltyAdj,
+    /// balance correction made by the bookkeeper
+    JournalEntry,
+    /// on-chain fees of the account
+    OnchainFee,
+    /// fees paid for a payment of an invoice
+    InvoiceFee,
+    /// fees paid to rebalance a channel
+    RebalanceFee,
+    /// a tag added by a newer version of the plugin
+    #[serde(other)]
+    Unknown,
+}
+
+/// Format of the file written by 'bkpr-dumpincomecsv'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvFormat {
+    /// CoinTracker
+    CoinTracker,
+    /// Koinly
+    Koinly,
+    /// Harmony
+    Harmony,
+    /// QuickBooks
+    QuickBooks,
+}
+
+impl<C: Transport> LightningRPC<C> {
+    /// Show the current balance of every account, the wallet and each channel.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_listbalances(&self) -> Result<responses::ListBalances, Error> {
+        self.call("bkpr-listbalances", requests::ListBalances {})
+    }
+
+    /// Show the coin movements of {account} (or of all accounts, if no {account}),
+    /// only those of the payment {payment_id} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_listaccountevents(
+        &self,
+        account: Option<&str>,
+        payment_id: Option<&str>,
+    ) -> Result<responses::ListAccountEvents, Error> {
+        self.call(
+            "bkpr-listaccountevents",
+            requests::ListAccountEvents {
+                account,
+                payment_id,
+            },
+        )
+    }
+
+    /// Show the income events (credits and debits of the node, transfers between
+    /// its accounts left out) between {start_time} and {end_time}, merging the
+    /// on-chain fees of each transaction if {consolidate_fees} (the default).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_listincome(
+        &self,
+        consolidate_fees: Option<bool>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::ListIncome, Error> {
+        self.call(
+            "bkpr-listincome",
+            requests::ListIncome {
+                consolidate_fees,
+                start_time,
+                end_time,
+            },
+        )
+    }
+
+    /// Show the routing statistics and APY of each channel between {start_time}
+    /// and {end_time}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_channelsapy(
+        &self,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::ChannelsApy, Error> {
+        self.call(
+            "bkpr-channelsapy",
+            requests::ChannelsApy {
+                start_time,
+                end_time,
+            },
+        )
+    }
+
+    /// Write the income events (see bkpr_listincome()) to {csv_file} in {csv_format},
+    /// the plugin picks the file name if no {csv_file}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_dumpincomecsv(
+        &self,
+        csv_format: CsvFormat,
+        csv_file: Option<&str>,
+        consolidate_fees: Option<bool>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<responses::DumpIncomeCsv, Error> {
+        self.call(
+            "bkpr-dumpincomecsv",
+            requests::DumpIncomeCsv {
+                csv_format,
+                csv_file,
+                consolidate_fees,
+                start_time,
+                end_time,
+            },
+        )
+    }
+
+    /// Show the on-chain transactions of the channel {account}, with their fees
+    /// and outputs.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_inspect(&self, account: &str) -> Result<responses::Inspect, Error> {
+        self.call("bkpr-inspect", requests::Inspect { account })
+    }
+
+    /// Set the {description} of the events of the payment {payment_id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn bkpr_editdescriptionbypaymentid(
+        &self,
+        payment_id: &str,
+        description: &str,
+    ) -> Result<responses::EditDescriptionByPaymentId, Error> {
+        self.call(
+            "bkpr-editdescriptionbypaymentid",
+            requests::EditDescriptionByPaymentId {
+                payment_id,
+                description,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{responses, CoinMovementTag, CsvFormat, EventType};
+    use crate::lightningrpc::tests::check_call;
+
+    #[test]
+    #[allow(deprecated)]
+    fn account_events_and_income() {
+        let events = check_call(
+            "bkpr-listaccountevents",
+            json!({ "account": "wallet" }),
+            json!({ "events": [
+                {
+                    "account": "wallet",
+                    "type": "chain",
+                    "tag": "deposit",
+                    "credit_msat": 200000000,
+                    "debit_msat": 0,
+                    "currency": "bcrt",
+                    "outpoint": "7d3d...:0",
+                    "timestamp": 1700000000,
+                    "blockheight": 110
+                },
+                {
+                    "account": "wallet",
+                    "type": "onchain_fee",
+                    "tag": "onchain_fee",
+                    "credit_msat": 0,
+                    "debit_msat": 1530000,
+                    "currency": "bcrt",
+                    "timestamp": 1700000100,
+                    "txid": "7d3d"
+                }
+            ]}),
+            |lightning| lightning.bkpr_listaccountevents(Some("wallet"), None),
+        )
+        .events;
+        assert_eq!(events[0].type_, EventType::Chain);
+        assert_eq!(events[0].tag, CoinMovementTag::Deposit);
+        assert_eq!(events[0].credit_msat.0, 200000000);
+        assert_eq!(events[1].type_, EventType::OnchainFee);
+        assert_eq!(events[1].tag, CoinMovementTag::OnchainFee);
+
+        let future: responses::AccountEvent = serde_json::from_value(json!({
+            "account": "wallet",
+            "type": "chain",
+            "tag": "some_future_tag",
+            "credit_msat": 0,
+            "debit_msat": 0,
+            "currency": "bcrt",
+            "timestamp": 1700000200
+        }))
+        .unwrap();
+        assert_eq!(future.tag, CoinMovementTag::Unknown);
+
+        let dump = check_call(
+            "bkpr-dumpincomecsv",
+            json!({ "csv_format": "koinly", "csv_file": "/tmp/income.csv" }),
+            json!({ "csv_file": "/tmp/income.csv", "csv_format": "koinly" }),
+            |lightning| {
+                lightning.bkpr_dumpincomecsv(
+                    CsvFormat::Koinly,
+                    Some("/tmp/income.csv"),
+                    None,
+                    None,
+                    None,
+                )
+            },
+        );
+        assert_eq!(dump.csv_format, CsvFormat::Koinly);
+    }
+}
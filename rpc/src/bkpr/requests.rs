@@ -0,0 +1,65 @@
+//! Structures representing requests to the bookkeeper commands
+#![allow(missing_docs)]
+use serde::{Deserialize, Serialize};
+
+use super::CsvFormat;
+
+/// 'bkpr-listbalances' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListBalances {}
+
+/// 'bkpr-listaccountevents' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListAccountEvents<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<&'a str>,
+}
+
+/// 'bkpr-listincome' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListIncome {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consolidate_fees: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<u64>,
+}
+
+/// 'bkpr-channelsapy' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelsApy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<u64>,
+}
+
+/// 'bkpr-dumpincomecsv' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DumpIncomeCsv<'a> {
+    pub csv_format: CsvFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csv_file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consolidate_fees: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<u64>,
+}
+
+/// 'bkpr-inspect' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Inspect<'a> {
+    pub account: &'a str,
+}
+
+/// 'bkpr-editdescriptionbypaymentid' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EditDescriptionByPaymentId<'a> {
+    pub payment_id: &'a str,
+    pub description: &'a str,
+}
@@ -0,0 +1,159 @@
+#![allow(missing_docs)]
+//! Structures representing responses to the bookkeeper commands
+use serde::{Deserialize, Serialize};
+
+use super::{CoinMovementTag, CsvFormat, EventType};
+use crate::types::MSat;
+
+/// Sub-structure for 'balances' in 'bkpr-listbalances'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Balance {
+    pub balance_msat: MSat,
+    pub coin_type: String,
+}
+
+/// Sub-structure for 'accounts' in 'bkpr-listbalances'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Account {
+    pub account: String,
+    pub peer_id: Option<String>,
+    pub we_opened: Option<bool>,
+    pub account_closed: Option<bool>,
+    pub account_resolved: Option<bool>,
+    pub resolved_at_block: Option<u32>,
+    pub balances: Vec<Balance>,
+}
+
+/// 'bkpr-listbalances' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListBalances {
+    pub accounts: Vec<Account>,
+}
+
+/// Sub-structure for 'events' in 'bkpr-listaccountevents'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountEvent {
+    pub account: String,
+    #[serde(rename = "type")]
+    pub type_: EventType,
+    pub tag: CoinMovementTag,
+    pub credit_msat: MSat,
+    pub debit_msat: MSat,
+    pub currency: String,
+    pub timestamp: u64,
+    pub outpoint: Option<String>,
+    pub blockheight: Option<u32>,
+    pub origin: Option<String>,
+    pub payment_id: Option<String>,
+    pub txid: Option<String>,
+    pub description: Option<String>,
+    pub fees_msat: Option<MSat>,
+    pub is_rebalance: Option<bool>,
+    pub part_id: Option<u64>,
+}
+
+/// 'bkpr-listaccountevents' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListAccountEvents {
+    pub events: Vec<AccountEvent>,
+}
+
+/// Sub-structure for 'income_events' in 'bkpr-listincome'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IncomeEvent {
+    pub account: String,
+    pub tag: CoinMovementTag,
+    pub credit_msat: MSat,
+    pub debit_msat: MSat,
+    pub currency: String,
+    pub timestamp: u64,
+    pub description: Option<String>,
+    pub outpoint: Option<String>,
+    pub txid: Option<String>,
+    pub payment_id: Option<String>,
+}
+
+/// 'bkpr-listincome' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListIncome {
+    pub income_events: Vec<IncomeEvent>,
+}
+
+/// Sub-structure for 'channels_apy' in 'bkpr-channelsapy'
+///
+/// The utilizations and APYs are percentages formatted by the plugin, e.g. "1.2345%".
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelApy {
+    pub account: String,
+    pub routed_out_msat: MSat,
+    pub routed_in_msat: MSat,
+    pub lease_fee_paid_msat: MSat,
+    pub lease_fee_earned_msat: MSat,
+    pub pushed_out_msat: MSat,
+    pub pushed_in_msat: MSat,
+    pub our_start_balance_msat: MSat,
+    pub channel_start_balance_msat: MSat,
+    pub fees_out_msat: MSat,
+    pub fees_in_msat: Option<MSat>,
+    pub utilization_out: String,
+    pub utilization_out_initial: Option<String>,
+    pub utilization_in: String,
+    pub utilization_in_initial: Option<String>,
+    pub apy_out: String,
+    pub apy_out_initial: Option<String>,
+    pub apy_in: String,
+    pub apy_in_initial: Option<String>,
+    pub apy_total: String,
+    pub apy_total_initial: Option<String>,
+    pub apy_lease: Option<String>,
+}
+
+/// 'bkpr-channelsapy' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelsApy {
+    pub channels_apy: Vec<ChannelApy>,
+}
+
+/// 'bkpr-dumpincomecsv' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DumpIncomeCsv {
+    pub csv_file: String,
+    pub csv_format: CsvFormat,
+}
+
+/// Sub-structure for 'outputs' in 'bkpr-inspect'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InspectOutput {
+    pub account: String,
+    pub outnum: u32,
+    pub output_value_msat: MSat,
+    pub currency: String,
+    pub credit_msat: Option<MSat>,
+    pub debit_msat: Option<MSat>,
+    pub originating_account: Option<String>,
+    pub output_tag: Option<CoinMovementTag>,
+    pub spend_tag: Option<CoinMovementTag>,
+    pub spending_txid: Option<String>,
+    pub payment_id: Option<String>,
+}
+
+/// Sub-structure for 'txs' in 'bkpr-inspect'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InspectTx {
+    pub txid: String,
+    pub blockheight: Option<u32>,
+    pub fees_paid_msat: MSat,
+    pub outputs: Vec<InspectOutput>,
+}
+
+/// 'bkpr-inspect' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Inspect {
+    pub txs: Vec<InspectTx>,
+}
+
+/// 'bkpr-editdescriptionbypaymentid' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EditDescriptionByPaymentId {
+    pub updated: Vec<AccountEvent>,
+}
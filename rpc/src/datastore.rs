@@ -0,0 +1,245 @@
+//! Typed interface to the lightningd datastore
+//!
+//! `Datastore<T>` keeps values of type `T`, serialized as JSON strings,
+//! under a common key prefix (e.g. the name of the plugin owning them).
+//! Each value is read together with its generation, which can be used to
+//! replace it only if nobody changed it in the meantime.
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeOwned};
+use serde::Serialize;
+
+use clightningrpc_common::client;
+use clightningrpc_common::errors::Error;
+use clightningrpc_common::transport::Transport;
+
+use crate::lightningrpc::LightningRPC;
+use crate::requests::{DatastoreData, DatastoreMode};
+use crate::responses;
+
+/// A value read from the datastore, with its generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<T> {
+    /// the stored value
+    pub value: T,
+    /// the generation of the entry, increased by every change
+    pub generation: u64,
+}
+
+/// Values of type `T` stored under a key prefix of the datastore
+#[derive(Debug)]
+pub struct Datastore<'a, T, C = client::Client> {
+    rpc: &'a LightningRPC<C>,
+    prefix: Vec<String>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, C> Datastore<'a, T, C>
+where
+    T: Serialize + DeserializeOwned,
+    C: Transport,
+{
+    /// Creates a datastore keeping its values under the key {prefix}
+    pub fn new(rpc: &'a LightningRPC<C>, prefix: &[&str]) -> Datastore<'a, T, C> {
+        Datastore {
+            rpc,
+            prefix: prefix.iter().map(|key| key.to_string()).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Read the value {name}, `None` if it does not exist
+    #[allow(deprecated)]
+    pub fn get(&self, name: &str) -> Result<Option<Entry<T>>, Error> {
+        let key = key(&self.prefix, name);
+        let entries = self.rpc.listdatastore(Some(&key))?.datastore;
+        entry(&key, entries)
+    }
+
+    /// Store {value} as {name}, replacing the current value if any,
+    /// and return the new generation
+    pub fn set(&self, name: &str, value: &T) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::CreateOrReplace, None)
+    }
+
+    /// Store {value} as {name}, failing if it already exists,
+    /// and return the new generation
+    pub fn create(&self, name: &str, value: &T) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::MustCreate, None)
+    }
+
+    /// Replace {name} with {value} only if it is still at {generation},
+    /// and return the new generation
+    pub fn replace(&self, name: &str, value: &T, generation: u64) -> Result<u64, Error> {
+        self.store(name, value, DatastoreMode::MustReplace, Some(generation))
+    }
+
+    /// Remove {name}, only if it is still at {generation} if given
+    #[allow(deprecated)]
+    pub fn remove(&self, name: &str, generation: Option<u64>) -> Result<(), Error> {
+        self.rpc
+            .deldatastore(&key(&self.prefix, name), generation)
+            .map(|_| ())
+    }
+
+    /// Names of the values stored under the prefix
+    #[allow(deprecated)]
+    pub fn names(&self) -> Result<Vec<String>, Error> {
+        let entries = self.rpc.listdatastore(Some(&self.prefix))?.datastore;
+        Ok(names(&self.prefix, entries))
+    }
+
+    #[allow(deprecated)]
+    fn store(
+        &self,
+        name: &str,
+        value: &T,
+        mode: DatastoreMode,
+        generation: Option<u64>,
+    ) -> Result<u64, Error> {
+        let value = serde_json::to_string(value)?;
+        let stored = self.rpc.datastore(
+            &key(&self.prefix, name),
+            DatastoreData::String(&value),
+            Some(mode),
+            generation,
+        )?;
+        generation_of(stored.generation)
+    }
+}
+
+pub(crate) fn key<'k>(prefix: &'k [String], name: &'k str) -> Vec<&'k str> {
+    prefix
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(name))
+        .collect()
+}
+
+/// Decode the entry at {key} out of the 'listdatastore' {entries}
+pub(crate) fn entry<T: DeserializeOwned>(
+    key: &[&str],
+    entries: Vec<responses::Datastore>,
+) -> Result<Option<Entry<T>>, Error> {
+    let found = entries.into_iter().find(|entry| entry.key == key);
+    match found {
+        Some(responses::Datastore {
+            string: Some(string),
+            generation,
+            ..
+        }) => Ok(Some(Entry {
+            value: serde_json::from_str(&string)?,
+            generation: generation_of(generation)?,
+        })),
+        // no entry, or a parent key without data
+        _ => Ok(None),
+    }
+}
+
+/// The {generation} lightningd returns with every stored value, which can not
+/// be made up as a later replace() would then fail or overwrite a newer value
+pub(crate) fn generation_of(generation: Option<u64>) -> Result<u64, Error> {
+    generation.ok_or_else(|| <serde_json::Error as de::Error>::missing_field("generation").into())
+}
+
+/// Names of the direct children of {prefix} in the 'listdatastore' {entries}
+pub(crate) fn names(prefix: &[String], entries: Vec<responses::Datastore>) -> Vec<String> {
+    entries
+        .into_iter()
+        .filter(|entry| entry.key.len() == prefix.len() + 1 && entry.key.starts_with(prefix))
+        .filter_map(|mut entry| entry.key.pop())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clightningrpc_common::errors::Error;
+    use clightningrpc_common::transport::Mock;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::{Datastore, Entry};
+    use crate::lightningrpc::LightningRPC;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    struct Lease {
+        peer: String,
+        sat: u64,
+    }
+
+    #[test]
+    fn typed_values_and_generations() {
+        let mut lightning = LightningRPC::with_transport(Mock::new());
+        let lease = Lease {
+            peer: "02aa".to_owned(),
+            sat: 100000,
+        };
+        lightning.client().expect(
+            "listdatastore",
+            json!({ "datastore": [{
+                "key": ["lsp", "leases", "cc"],
+                "generation": 3,
+                "hex": "",
+                "string": serde_json::to_string(&lease).unwrap()
+            }]}),
+        );
+        lightning.client().expect(
+            "datastore",
+            json!({ "key": ["lsp", "leases", "cc"], "generation": 4 }),
+        );
+        lightning.client().expect(
+            "listdatastore",
+            json!({ "datastore": [
+                { "key": ["lsp", "leases"] },
+                { "key": ["lsp", "leases", "cc"], "generation": 4, "string": "{}" },
+                { "key": ["lsp", "leases", "dd"], "generation": 0, "string": "{}" }
+            ]}),
+        );
+        lightning
+            .client()
+            .expect("listdatastore", json!({ "datastore": [] }));
+        lightning.client().expect(
+            "listdatastore",
+            json!({ "datastore": [{
+                "key": ["lsp", "leases", "ff"],
+                "string": serde_json::to_string(&lease).unwrap()
+            }]}),
+        );
+        lightning.client().expect(
+            "datastore",
+            json!({ "key": ["lsp", "leases", "ff"], "string": "{}" }),
+        );
+
+        let leases: Datastore<Lease, _> = Datastore::new(&lightning, &["lsp", "leases"]);
+
+        let entry = leases.get("cc").unwrap().unwrap();
+        assert_eq!(
+            entry,
+            Entry {
+                value: lease.clone(),
+                generation: 3
+            }
+        );
+        assert_eq!(leases.replace("cc", &lease, entry.generation).unwrap(), 4);
+        assert_eq!(leases.names().unwrap(), ["cc", "dd"]);
+        assert!(leases.get("ee").unwrap().is_none());
+        // a value without generation is not reported at generation 0
+        match leases.get("ff") {
+            Err(Error::Json(err)) => assert!(err.to_string().contains("generation")),
+            other => panic!("unexpected entry {:?}", other),
+        }
+        assert!(matches!(leases.set("ff", &lease), Err(Error::Json(_))));
+
+        let requests = lightning.client().requests();
+        assert_eq!(requests[0].1, json!({ "key": ["lsp", "leases", "cc"] }));
+        assert_eq!(
+            requests[1].1,
+            json!({
+                "key": ["lsp", "leases", "cc"],
+                "string": "{\"peer\":\"02aa\",\"sat\":100000}",
+                "mode": "must-replace",
+                "generation": 3
+            })
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Decoding and encoding of the binary data lightningd passes around as text
+//!
+//! Each alphabet has a 256 entries lookup table from the character to its
+//! value, so decoding is a single pass without searching the alphabet.
+//! The decoders return `None` as soon as a character is outside of it.
+
+/// Marks the characters outside of an alphabet in its lookup table
+const INVALID: u8 = 0xff;
+
+/// Build the table giving the value of each character of {alphabet}
+const fn lookup_table(alphabet: &[u8]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_TABLE: [u8; 256] = lookup_table(BASE64_ALPHABET);
+const BASE64URL_TABLE: [u8; 256] =
+    lookup_table(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+const ZBASE32_TABLE: [u8; 256] = lookup_table(b"ybndrfg8ejkmcpqxot1uwisza345h769");
+const HEX_TABLE: [u8; 256] = lookup_table(b"0123456789abcdef");
+
+/// Decode {data}, each character carrying {bits} bits of the output, and drop
+/// the trailing bits that do not fill a byte
+fn decode_bits(data: &[u8], table: &[u8; 256], bits: u32) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(data.len() * bits as usize / 8);
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    for c in data {
+        let value = table[*c as usize];
+        if value == INVALID {
+            return None;
+        }
+        buffer = (buffer << bits) | u32::from(value);
+        buffered += bits;
+        if buffered >= 8 {
+            buffered -= 8;
+            bytes.push((buffer >> buffered) as u8);
+            buffer &= (1 << buffered) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// Decode standard, padded base64
+pub(crate) fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let data = s.as_bytes();
+    // base64 comes in groups of four characters
+    if !data.chunks_exact(4).remainder().is_empty() {
+        return None;
+    }
+    let padding = data.iter().rev().take_while(|c| **c == b'=').count();
+    if padding > 2 {
+        return None;
+    }
+    decode_bits(&data[..data.len() - padding], &BASE64_TABLE, 6)
+}
+
+/// Decode base64url, with or without the padding
+pub(crate) fn base64url_decode(s: &str) -> Option<Vec<u8>> {
+    let data = s.as_bytes();
+    let padding = data.iter().rev().take_while(|c| **c == b'=').count();
+    let data = &data[..data.len() - padding];
+    // a single character does not fill a byte, and padding only completes a group
+    if data.len() % 4 == 1 || (padding > 0 && data.len() % 4 + padding != 4) {
+        return None;
+    }
+    decode_bits(data, &BASE64URL_TABLE, 6)
+}
+
+/// Decode zbase32, without the trailing bits that do not fill a byte
+pub(crate) fn zbase32_decode(s: &str) -> Option<Vec<u8>> {
+    decode_bits(s.as_bytes(), &ZBASE32_TABLE, 5)
+}
+
+/// Decode lowercase hex
+pub(crate) fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    let data = s.as_bytes();
+    // two characters for each byte
+    if !data.chunks_exact(2).remainder().is_empty() {
+        return None;
+    }
+    decode_bits(data, &HEX_TABLE, 4)
+}
+
+/// Encode {bytes} as standard, padded base64
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_decode, base64_encode, base64url_decode, hex_decode, zbase32_decode};
+
+    #[test]
+    fn base64_round_trip() {
+        for bytes in [&b""[..], b"f", b"fo", b"foo", b"foob", b"\xff\x00\xfe"] {
+            assert_eq!(base64_decode(&base64_encode(bytes)).unwrap(), bytes);
+        }
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_decode("Zm8="), Some(b"fo".to_vec()));
+        assert_eq!(base64_decode("Zm8"), None);
+        assert_eq!(base64_decode("Z=8="), None);
+        assert_eq!(base64_decode("Z==="), None);
+        assert_eq!(base64_decode("Zm-_"), None);
+        assert_eq!(base64_decode("Zm\u{e9}"), None);
+    }
+
+    #[test]
+    fn base64url_with_and_without_padding() {
+        assert_eq!(base64url_decode("-_8"), Some(vec![0xfb, 0xff]));
+        assert_eq!(base64url_decode("-_8="), Some(vec![0xfb, 0xff]));
+        assert_eq!(base64url_decode("-_8=="), None);
+        assert_eq!(base64url_decode("Zm8AZ"), None);
+        assert_eq!(base64url_decode("+/8="), None);
+    }
+
+    #[test]
+    fn zbase32_and_hex() {
+        assert_eq!(zbase32_decode("yy"), Some(vec![0]));
+        assert_eq!(zbase32_decode("9999"), Some(vec![0xff, 0xff]));
+        assert_eq!(zbase32_decode("y0"), None);
+        assert_eq!(hex_decode("00ffa0"), Some(vec![0x00, 0xff, 0xa0]));
+        assert_eq!(hex_decode("00FF"), None);
+        assert_eq!(hex_decode("abc"), None);
+    }
+}
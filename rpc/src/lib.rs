@@ -31,11 +31,17 @@
 extern crate serde;
 extern crate serde_json;
 
+#[cfg(feature = "bkpr")]
+pub mod bkpr;
+pub mod datastore;
 pub mod lightningrpc;
 pub mod requests;
 pub mod responses;
+pub mod rune;
 pub mod types;
 
+mod encoding;
+
 #[cfg(feature = "async")]
 pub mod r#async;
 
@@ -1,20 +1,38 @@
 //! High-level interface to c-lightning RPC
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
 use clightningrpc_common::client;
 use clightningrpc_common::errors::Error;
+use clightningrpc_common::filter::filter_for;
+use clightningrpc_common::transport::Transport;
+use clightningrpc_common::types::{BatchEntry, BatchRequest, BatchResponse};
 
 use crate::requests;
 use crate::responses;
-use crate::types::RouteItem;
+use crate::rune::Restriction;
+use crate::types::{
+    AskreneInform, CustomMessage, ForwardStatus, Hex, PluginSubcommand, Psbt, RouteHintHop,
+    RouteItem, WaitIndex, WaitSubsystem,
+};
+
+/// Number of openchannel_update() or splice_update() calls after which
+/// open_dual_funded_channel() and splice_channel() give up
+pub const MAX_UPDATE_ROUNDS: usize = 16;
 
 /// Structure providing a high-level interface to the c-lightning daemon RPC
+///
+/// The requests are sent through the transport `T`, by default the
+/// lightning-rpc socket, see `clightningrpc_common::transport`.
 #[derive(Debug)]
-pub struct LightningRPC {
-    client: client::Client,
+pub struct LightningRPC<T = client::Client> {
+    client: T,
+    /// send a `filter` derived from the response type with every call
+    filter_responses: bool,
 }
 
 /// Optional arguments for pay() request
@@ -36,22 +54,301 @@ pub struct PayOptions<'f> {
     pub maxdelay: Option<u64>,
 }
 
+/// Optional arguments for keysend() request
+#[derive(Debug, Clone, Default)]
+pub struct KeysendOptions<'f> {
+    /// {label} to identify the payment in listpays
+    pub label: Option<&'f str>,
+    /// {maxfeepercent} (default 0.5) the maximum acceptable fee as a percentage (e.g. 0.5 => 0.5%)
+    pub maxfeepercent: Option<f64>,
+    /// {retry_for} (default 60) the integer number of seconds before we stop retrying
+    pub retry_for: Option<u64>,
+    /// {maxdelay} (default 500) the maximum number of blocks we allow the funds to possibly get locked
+    pub maxdelay: Option<u64>,
+    /// {exemptfee} (default 5000 msat) disables the maxfeepercent check for fees below the threshold
+    pub exemptfee: Option<u64>,
+    /// {maxfee} the maximum fee in millisatoshi, overriding maxfeepercent and exemptfee
+    pub maxfee: Option<u64>,
+    /// {routehints} the routes to reach a destination without public channels
+    pub routehints: Option<Vec<Vec<RouteHintHop>>>,
+    /// {extratlvs} the extra TLV records to send to the destination, by type
+    pub extratlvs: Option<BTreeMap<u64, String>>,
+}
+
+/// Optional arguments for xpay() request
+#[derive(Debug, Clone, Default)]
+pub struct XPayOptions<'f> {
+    /// {amount_msat} (if and only if the invoice does not have amount)
+    pub amount_msat: Option<u64>,
+    /// {maxfee} (default 5000 msat or 1%) the maximum fee in millisatoshi
+    pub maxfee: Option<u64>,
+    /// {layers} the askrene layers to use when routing the payment
+    pub layers: Option<Vec<&'f str>>,
+    /// {retry_for} (default 60) the integer number of seconds before we stop retrying
+    pub retry_for: Option<u64>,
+    /// {partial_msat} pay only this part of the invoice, the rest is paid by other nodes
+    pub partial_msat: Option<u64>,
+    /// {maxdelay} the maximum number of blocks we allow the funds to possibly get locked
+    pub maxdelay: Option<u64>,
+}
+
+/// Optional arguments for offer() request
+#[derive(Debug, Clone, Default)]
+pub struct OfferOptions<'f> {
+    /// {issuer} who is issuing this offer (i.e. you)
+    pub issuer: Option<&'f str>,
+    /// {label} internal-use name for the offer
+    pub label: Option<&'f str>,
+    /// {quantity_max} the maximum quantity of items that can be requested in a single invoice
+    pub quantity_max: Option<u64>,
+    /// {absolute_expiry} UNIX timestamp after which the offer can not be used anymore
+    pub absolute_expiry: Option<u64>,
+    /// {recurrence} the period of a recurring offer, e.g. `1month`
+    pub recurrence: Option<&'f str>,
+    /// {recurrence_base} the UNIX timestamp of the first recurrence period
+    pub recurrence_base: Option<u64>,
+    /// {recurrence_paywindow} the window in which each period can be paid, e.g. `-3600+86400`
+    pub recurrence_paywindow: Option<&'f str>,
+    /// {recurrence_limit} the number of the last period that can be paid
+    pub recurrence_limit: Option<u64>,
+    /// {single_use} (default false) expire the offer after the first payment
+    pub single_use: Option<bool>,
+}
+
+/// Optional arguments for fetchinvoice() request
+#[derive(Debug, Clone, Default)]
+pub struct FetchInvoiceOptions<'f> {
+    /// {amount_msat} (if and only if the offer does not have an amount)
+    pub amount_msat: Option<u64>,
+    /// {quantity} of items to request (if and only if the offer has a `quantity_max`)
+    pub quantity: Option<u64>,
+    /// {recurrence_counter} the period to pay, starting from 0 (recurring offers only)
+    pub recurrence_counter: Option<u64>,
+    /// {recurrence_start} the first period to pay (offers with `recurrence_base` only)
+    pub recurrence_start: Option<u64>,
+    /// {recurrence_label} label identifying all the payments of a recurring offer
+    pub recurrence_label: Option<&'f str>,
+    /// {timeout} (default 60) the number of seconds to wait for the invoice
+    pub timeout: Option<f64>,
+    /// {payer_note} note for the payee
+    pub payer_note: Option<&'f str>,
+}
+
+/// Optional arguments for invoicerequest() request
+#[derive(Debug, Clone, Default)]
+pub struct InvoiceRequestOptions<'f> {
+    /// {issuer} who is issuing this invoice request (i.e. you)
+    pub issuer: Option<&'f str>,
+    /// {label} internal-use name for the invoice request
+    pub label: Option<&'f str>,
+    /// {absolute_expiry} UNIX timestamp after which the invoice request can not be used anymore
+    pub absolute_expiry: Option<u64>,
+    /// {single_use} (default true) expire the invoice request after the first payment
+    pub single_use: Option<bool>,
+}
+
+/// Optional arguments for fundpsbt() request
+#[derive(Debug, Clone, Default)]
+pub struct FundPsbtOptions {
+    /// {minconf} (default 1) minimum number of confirmations of the used outputs
+    pub minconf: Option<u32>,
+    /// {reserve} (default 72) number of blocks to reserve the used outputs for, 0 to not reserve
+    pub reserve: Option<u32>,
+    /// {locktime} of the transaction
+    pub locktime: Option<u32>,
+    /// {min_witness_weight} (default 0) minimum weight to assume for each input witness
+    pub min_witness_weight: Option<u32>,
+    /// {excess_as_change} (default false) add a change output for the excess funds
+    pub excess_as_change: Option<bool>,
+    /// {nonwrapped} (default false) only use native segwit outputs
+    pub nonwrapped: Option<bool>,
+    /// {opening_anchor_channel} (default false) keep the emergency reserve for anchor channels
+    pub opening_anchor_channel: Option<bool>,
+}
+
+/// Optional arguments for utxopsbt() request
+#[derive(Debug, Clone, Default)]
+pub struct UtxoPsbtOptions {
+    /// {reserve} (default 72) number of blocks to reserve the used outputs for, 0 to not reserve
+    pub reserve: Option<u32>,
+    /// {reservedok} (default false) allow outputs that are already reserved
+    pub reservedok: Option<bool>,
+    /// {locktime} of the transaction
+    pub locktime: Option<u32>,
+    /// {min_witness_weight} (default 0) minimum weight to assume for each input witness
+    pub min_witness_weight: Option<u32>,
+    /// {excess_as_change} (default false) add a change output for the excess funds
+    pub excess_as_change: Option<bool>,
+    /// {opening_anchor_channel} (default false) keep the emergency reserve for anchor channels
+    pub opening_anchor_channel: Option<bool>,
+}
+
+/// Optional arguments for multifundchannel() request
+#[derive(Debug, Clone, Default)]
+pub struct MultiFundChannelOptions<'f> {
+    /// {feerate} of the funding transaction
+    pub feerate: Option<&'f str>,
+    /// {minconf} (default 1) minimum number of confirmations of the used outputs
+    pub minconf: Option<u32>,
+    /// {utxos} to fund the channels from, as `txid:vout`
+    pub utxos: Option<Vec<&'f str>>,
+    /// {minchannels} (default all) minimum number of channels to open, skipping the failed peers
+    pub minchannels: Option<u32>,
+    /// {commitment_feerate} of the initial commitment transactions
+    pub commitment_feerate: Option<&'f str>,
+}
+
+/// Optional arguments for fundchannel_start() request
+#[derive(Debug, Clone, Default)]
+pub struct FundChannelStartOptions<'f> {
+    /// {feerate} of the initial commitment transaction
+    pub feerate: Option<&'f str>,
+    /// {announce} (default true) whether to announce the channel
+    pub announce: Option<bool>,
+    /// {close_to} address to send our funds to on a mutual close
+    pub close_to: Option<&'f str>,
+    /// {push_msat} amount to give to the peer on opening
+    pub push_msat: Option<u64>,
+    /// {mindepth} number of confirmations before the channel is usable
+    pub mindepth: Option<u32>,
+    /// {reserve} amount in satoshi the peer must keep in the channel
+    pub reserve: Option<u64>,
+    /// {channel_type} feature bits of the channel type to negotiate
+    pub channel_type: Option<Vec<u32>>,
+}
+
+/// Optional arguments for openchannel_init() request
+#[derive(Debug, Clone, Default)]
+pub struct OpenChannelOptions<'f> {
+    /// {commitment_feerate} of the initial commitment transactions
+    pub commitment_feerate: Option<&'f str>,
+    /// {funding_feerate} of the funding transaction
+    pub funding_feerate: Option<&'f str>,
+    /// {announce} (default true) whether to announce the channel
+    pub announce: Option<bool>,
+    /// {close_to} address to send our funds to on a mutual close
+    pub close_to: Option<&'f str>,
+    /// {request_amt} amount in satoshi to lease from the peer
+    pub request_amt: Option<u64>,
+    /// {compact_lease} the peer's advertised lease rates, required with {request_amt}
+    pub compact_lease: Option<&'f str>,
+    /// {channel_type} feature bits of the channel type to negotiate
+    pub channel_type: Option<Vec<u32>>,
+}
+
+/// Optional arguments for listforwards() request
+#[derive(Debug, Clone, Default)]
+pub struct ListForwardsOptions<'f> {
+    /// {status} only show the forwards with this status
+    pub status: Option<ForwardStatus>,
+    /// {in_channel} only show the forwards coming from this short channel id
+    pub in_channel: Option<&'f str>,
+    /// {out_channel} only show the forwards going to this short channel id
+    pub out_channel: Option<&'f str>,
+    /// {index}, {start} and {limit} only show a page of the forwards
+    pub pagination: Option<requests::Pagination>,
+}
+
+/// Optional arguments for setchannel() request
+#[derive(Debug, Clone, Default)]
+pub struct SetChannelOptions {
+    /// {feebase} base fee in millisatoshi
+    pub feebase: Option<u64>,
+    /// {feeppm} proportional fee in millionths
+    pub feeppm: Option<u32>,
+    /// {htlcmin} minimum HTLC to forward, in millisatoshi
+    pub htlcmin: Option<u64>,
+    /// {htlcmax} maximum HTLC to forward, in millisatoshi
+    pub htlcmax: Option<u64>,
+    /// {enforcedelay} (default 600) seconds during which the previous fees are still accepted
+    pub enforcedelay: Option<u32>,
+    /// {ignorefeelimits} (default false) allow the peer to set any commitment feerate
+    pub ignorefeelimits: Option<bool>,
+}
+
+/// Optional arguments for getroutes() request
+#[derive(Debug, Clone, Default)]
+pub struct GetRoutesOptions {
+    /// {maxdelay} maximum total CLTV delay of a route
+    pub maxdelay: Option<u32>,
+    /// {maxparts} maximum number of parts the payment is split in
+    pub maxparts: Option<u32>,
+}
+
+/// Optional arguments for askrene_update_channel() request, the fields not set keep
+/// their gossip value
+#[derive(Debug, Clone, Default)]
+pub struct AskreneUpdateChannelOptions {
+    /// {enabled} whether the channel can be used
+    pub enabled: Option<bool>,
+    /// {htlc_minimum_msat} minimum HTLC forwarded, in millisatoshi
+    pub htlc_minimum_msat: Option<u64>,
+    /// {htlc_maximum_msat} maximum HTLC forwarded, in millisatoshi
+    pub htlc_maximum_msat: Option<u64>,
+    /// {fee_base_msat} base fee in millisatoshi
+    pub fee_base_msat: Option<u64>,
+    /// {fee_proportional_millionths} proportional fee in millionths
+    pub fee_proportional_millionths: Option<u32>,
+    /// {cltv_expiry_delta} CLTV delta required by the channel
+    pub cltv_expiry_delta: Option<u16>,
+}
+
+/// Builder for a batch of calls sent together to lightningd, see `LightningRPC::batch`
+#[derive(Debug)]
+pub struct Batch<'a, C = client::Client> {
+    client: &'a C,
+    request: BatchRequest,
+}
+
+impl<C: Transport> Batch<'_, C> {
+    /// Add a call to the batch, returning the handle used to take its result
+    /// from the `BatchResponse`.
+    pub fn call<T: Serialize, U: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        input: T,
+    ) -> Result<BatchEntry<U>, Error> {
+        self.request.add(method, input)
+    }
+
+    /// Send all the calls of the batch and wait for their results.
+    pub fn send(self) -> Result<BatchResponse, Error> {
+        self.client.send_batch(&self.request)
+    }
+}
+
 impl LightningRPC {
     /// Create a new connection from a UNIX socket path.
     ///
     /// # Arguments
     ///
     /// * `sockpath` - Path of UNIX socket to connect to, by default lightningd will create a
-    /// socket named `.lightning/lightning-rpc` in the home directory of the user running
-    /// lightningd.
+    ///   socket named `.lightning/lightning-rpc` in the home directory of the user running
+    ///   lightningd.
     pub fn new<P: AsRef<Path>>(sockpath: P) -> LightningRPC {
+        LightningRPC::with_transport(client::Client::new(sockpath))
+    }
+}
+
+impl<C: Transport> LightningRPC<C> {
+    /// Create a new interface sending the requests through `transport`,
+    /// e.g. a TCP socket, a remote node reached with commando or a mock.
+    pub fn with_transport(transport: C) -> LightningRPC<C> {
         LightningRPC {
-            client: client::Client::new(sockpath),
+            client: transport,
+            filter_responses: false,
         }
     }
 
+    /// Ask lightningd to return only the fields known by the response type of
+    /// each call, which makes large responses (e.g. `listchannels` or `listnodes`)
+    /// much smaller. See `clightningrpc_common::filter`.
+    pub fn set_filter_responses(&mut self, filter_responses: bool) {
+        self.filter_responses = filter_responses;
+    }
+
     /// Get reference to the low-level client connection
-    pub fn client(&mut self) -> &mut client::Client {
+    pub fn client(&mut self) -> &mut C {
         &mut self.client
     }
 
@@ -61,9 +358,67 @@ impl LightningRPC {
         method: &str,
         input: T,
     ) -> Result<U, Error> {
-        self.client
-            .send_request(method, input)
-            .and_then(|res| res.into_result())
+        let filter = if self.filter_responses {
+            filter_for::<U>()
+        } else {
+            None
+        };
+        self.call_with_filter(method, input, filter)
+    }
+
+    /// Generic call function for RPC calls that must not be repeated
+    /// (e.g. a payment), so they are never retried by the transport.
+    pub fn call_once<T: Serialize, U: DeserializeOwned>(
+        &self,
+        method: &str,
+        input: T,
+    ) -> Result<U, Error> {
+        let filter = if self.filter_responses {
+            filter_for::<U>()
+        } else {
+            None
+        };
+        let params = serde_json::to_value(input)?;
+        let result = self
+            .client
+            .send_once(method, params, filter)?
+            .into_result()?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Generic call function for RPC calls, returning only the fields
+    /// of the response listed in `filter`.
+    pub fn call_with_filter<T: Serialize, U: DeserializeOwned>(
+        &self,
+        method: &str,
+        input: T,
+        filter: Option<Value>,
+    ) -> Result<U, Error> {
+        let params = serde_json::to_value(input)?;
+        let result = self.client.send(method, params, filter)?.into_result()?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Start a batch of calls that are sent together to lightningd.
+    ///
+    /// ```no_run
+    /// use clightningrpc::lightningrpc::LightningRPC;
+    /// use clightningrpc::responses;
+    /// use serde_json::json;
+    ///
+    /// let rpc = LightningRPC::new("/tmp/lightning-rpc");
+    /// let mut batch = rpc.batch();
+    /// let info = batch.call::<_, responses::GetInfo>("getinfo", json!({})).unwrap();
+    /// let funds = batch.call::<_, responses::ListFunds>("listfunds", json!({})).unwrap();
+    /// let mut results = batch.send().unwrap();
+    /// println!("{:?}", results.take(info));
+    /// println!("{:?}", results.take(funds));
+    /// ```
+    pub fn batch(&self) -> Batch<'_, C> {
+        Batch {
+            client: &self.client,
+            request: BatchRequest::new(),
+        }
     }
 
     /// Show information about this node.
@@ -144,6 +499,127 @@ impl LightningRPC {
         self.call("listconfigs", requests::ListConfigs { config })
     }
 
+    /// List all configuration options, or with [config], just that one, with the source of
+    /// their value and whether they can be changed by setconfig().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listconfigs_v2(&self, config: Option<&str>) -> Result<responses::ListConfigsV2, Error> {
+        self.call("listconfigs", requests::ListConfigs { config })
+    }
+
+    /// Change the dynamic option {config} to {val} (or set the flag, if no {val}), also in
+    /// the config file unless {transient}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn setconfig(
+        &self,
+        config: &str,
+        val: Option<&str>,
+        transient: Option<bool>,
+    ) -> Result<responses::SetConfig, Error> {
+        self.call(
+            "setconfig",
+            requests::SetConfig {
+                config,
+                val,
+                transient,
+            },
+        )
+    }
+
+    /// Start the {plugin} executable, passing it the plugin {options}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn plugin_start(
+        &self,
+        plugin: &str,
+        options: &[(&str, &str)],
+    ) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Start,
+                plugin: Some(plugin),
+                directory: None,
+                options: options.iter().copied().collect(),
+            },
+        )
+    }
+
+    /// Stop the dynamic {plugin}, by name or path.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn plugin_stop(&self, plugin: &str) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Stop,
+                plugin: Some(plugin),
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+    }
+
+    /// Start all the plugins of {directory}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn plugin_startdir(&self, directory: &str) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Startdir,
+                plugin: None,
+                directory: Some(directory),
+                options: BTreeMap::new(),
+            },
+        )
+    }
+
+    /// Start the plugins of the plugin directories that are not running yet.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn plugin_rescan(&self) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::Rescan,
+                plugin: None,
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+    }
+
+    /// List the running plugins.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn plugin_list(&self) -> Result<responses::Plugin, Error> {
+        self.call(
+            "plugin",
+            requests::Plugin {
+                subcommand: PluginSubcommand::List,
+                plugin: None,
+                directory: None,
+                options: BTreeMap::new(),
+            },
+        )
+    }
+
     /// Show current peers, if {level} is set, include {log}s.
     #[deprecated(
         since = "0.1.0",
@@ -157,6 +633,86 @@ impl LightningRPC {
         self.call("listpeers", requests::ListPeers { id, level })
     }
 
+    /// Show the channels with our peers, or only the ones with peer {id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listpeerchannels(&self, id: Option<&str>) -> Result<responses::ListPeerChannels, Error> {
+        self.call("listpeerchannels", requests::ListPeerChannels { id })
+    }
+
+    /// Show the channels we closed, or only the ones with peer {id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listclosedchannels(
+        &self,
+        id: Option<&str>,
+    ) -> Result<responses::ListClosedChannels, Error> {
+        self.call("listclosedchannels", requests::ListClosedChannels { id })
+    }
+
+    /// Show the HTLCs forwarded by this node, see `ListForwardsOptions` for the filters.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listforwards(
+        &self,
+        options: ListForwardsOptions,
+    ) -> Result<responses::ListForwards, Error> {
+        self.call(
+            "listforwards",
+            requests::ListForwards {
+                status: options.status,
+                in_channel: options.in_channel,
+                out_channel: options.out_channel,
+                pagination: options.pagination,
+            },
+        )
+    }
+
+    /// Show the HTLCs of channel {id} (or of all channels, if no {id}), only a page of them
+    /// with {pagination}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listhtlcs(
+        &self,
+        id: Option<&str>,
+        pagination: Option<requests::Pagination>,
+    ) -> Result<responses::ListHtlcs, Error> {
+        self.call("listhtlcs", requests::ListHtlcs { id, pagination })
+    }
+
+    /// Change the fees and HTLC limits of channel {id} (a peer id, channel id, short channel
+    /// id or `all`).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn setchannel(
+        &self,
+        id: &str,
+        options: SetChannelOptions,
+    ) -> Result<responses::SetChannel, Error> {
+        self.call(
+            "setchannel",
+            requests::SetChannel {
+                id,
+                feebase: options.feebase,
+                feeppm: options.feeppm,
+                htlcmin: options.htlcmin,
+                htlcmax: options.htlcmax,
+                enforcedelay: options.enforcedelay,
+                ignorefeelimits: options.ignorefeelimits,
+            },
+        )
+    }
+
     /// Show invoice {label} (or all, if no {label)).
     #[deprecated(
         since = "0.1.0",
@@ -176,6 +732,33 @@ impl LightningRPC {
                 invstring,
                 payment_hash,
                 offer_id,
+                pagination: None,
+            },
+        )
+    }
+
+    /// Show a page of the invoices, selected by {pagination} among invoice {label}
+    /// (or all, if no {label}).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listinvoices_paginated(
+        &self,
+        label: Option<&str>,
+        invstring: Option<&str>,
+        payment_hash: Option<&str>,
+        offer_id: Option<&str>,
+        pagination: requests::Pagination,
+    ) -> Result<responses::ListInvoices, Error> {
+        self.call(
+            "listinvoices",
+            requests::ListInvoices {
+                label,
+                invstring,
+                payment_hash,
+                offer_id,
+                pagination: Some(pagination),
             },
         )
     }
@@ -322,7 +905,7 @@ impl LightningRPC {
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
     pub fn pay(&self, bolt11: &str, options: PayOptions) -> Result<responses::Pay, Error> {
-        self.call(
+        self.call_once(
             "pay",
             requests::Pay {
                 bolt11,
@@ -337,6 +920,55 @@ impl LightningRPC {
         )
     }
 
+    /// Send {amount_msat} to {destination} without an invoice, the preimage
+    /// is generated by us and sent along with the payment.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn keysend(
+        &self,
+        destination: &str,
+        amount_msat: u64,
+        options: KeysendOptions,
+    ) -> Result<responses::Keysend, Error> {
+        self.call_once(
+            "keysend",
+            requests::Keysend {
+                destination,
+                amount_msat,
+                label: options.label,
+                maxfeepercent: options.maxfeepercent,
+                retry_for: options.retry_for,
+                maxdelay: options.maxdelay,
+                exemptfee: options.exemptfee,
+                maxfee: options.maxfee,
+                routehints: options.routehints,
+                extratlvs: options.extratlvs,
+            },
+        )
+    }
+
+    /// Pay the bolt11 or bolt12 invoice {invstring} using the xpay payment engine.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn xpay(&self, invstring: &str, options: XPayOptions) -> Result<responses::XPay, Error> {
+        self.call_once(
+            "xpay",
+            requests::XPay {
+                invstring,
+                amount_msat: options.amount_msat,
+                maxfee: options.maxfee,
+                layers: options.layers,
+                retry_for: options.retry_for,
+                partial_msat: options.partial_msat,
+                maxdelay: options.maxdelay,
+            },
+        )
+    }
+
     /// Send along {route} in return for preimage of {payment_hash}, with optional {description}.
     #[deprecated(
         since = "0.1.0",
@@ -349,7 +981,7 @@ impl LightningRPC {
         description: Option<&str>,
         msatoshi: Option<u64>,
     ) -> Result<responses::SendPay, Error> {
-        self.call(
+        self.call_once(
             "sendpay",
             requests::SendPay {
                 route,
@@ -394,29 +1026,83 @@ impl LightningRPC {
             requests::ListSendPays {
                 bolt11,
                 payment_hash,
+                pagination: None,
             },
         )
     }
 
-    /// Decode {bolt11}, using {description} if necessary.
+    /// Show a page of the outgoing payments, selected by {pagination}.
     #[deprecated(
         since = "0.1.0",
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
-    pub fn decodepay(
+    pub fn listsendpays_paginated(
         &self,
-        bolt11: &str,
-        description: Option<&str>,
-    ) -> Result<responses::DecodePay, Error> {
+        bolt11: Option<&str>,
+        payment_hash: Option<&str>,
+        pagination: requests::Pagination,
+    ) -> Result<responses::ListSendPays, Error> {
         self.call(
-            "decodepay",
-            requests::DecodePay {
+            "listsendpays",
+            requests::ListSendPays {
                 bolt11,
-                description,
+                payment_hash,
+                pagination: Some(pagination),
+            },
+        )
+    }
+
+    /// Wait until the index {indexname} of {subsystem} reaches {nextvalue}, returning
+    /// the change that made it reach it.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn wait(
+        &self,
+        subsystem: WaitSubsystem,
+        indexname: WaitIndex,
+        nextvalue: u64,
+    ) -> Result<responses::Wait, Error> {
+        self.call(
+            "wait",
+            requests::Wait {
+                subsystem,
+                indexname,
+                nextvalue,
+            },
+        )
+    }
+
+    /// Decode {bolt11}, using {description} if necessary.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn decodepay(
+        &self,
+        bolt11: &str,
+        description: Option<&str>,
+    ) -> Result<responses::DecodePay, Error> {
+        self.call(
+            "decodepay",
+            requests::DecodePay {
+                bolt11,
+                description,
             },
         )
     }
 
+    /// Decode {string}, which can be a bolt11 or bolt12 string, a rune or an
+    /// emergency-recover blob.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn decode(&self, string: &str) -> Result<responses::Decoded, Error> {
+        self.call("decode", requests::Decode { string })
+    }
+
     /// Show route to {id} for {msatoshi}, using {riskfactor} and optional {cltv} (default 9). If
     /// specified search from {fromid} otherwise use this node as source. Randomize the route with
     /// up to {fuzzpercent} (0.0 -> 100.0, default 5.0) using {seed} as an arbitrary-size string
@@ -475,7 +1161,7 @@ impl LightningRPC {
     ///
     /// * `id` - Id of node to fund a channel to
     /// * `amount` - either `AmountOrAll::Amount(n)` for a given amount in satoshi units, or
-    /// `AmountOrAll::All` to spend all available funds
+    ///   `AmountOrAll::All` to spend all available funds
     /// * `feerate` - optional feerate to use for Bitcoin transaction
     #[deprecated(
         since = "0.1.0",
@@ -487,7 +1173,7 @@ impl LightningRPC {
         amount: requests::AmountOrAll,
         feerate: Option<u64>,
     ) -> Result<responses::FundChannel, Error> {
-        self.call(
+        self.call_once(
             "fundchannel",
             requests::FundChannel {
                 id,
@@ -510,7 +1196,7 @@ impl LightningRPC {
         force: Option<bool>,
         timeout: Option<u64>,
     ) -> Result<responses::Close, Error> {
-        self.call("close", requests::Close { id, force, timeout })
+        self.call_once("close", requests::Close { id, force, timeout })
     }
 
     /// Send {peerid} a ping of length {len} (default 128) asking for {pongbytes} (default 128).
@@ -542,7 +1228,7 @@ impl LightningRPC {
     ///
     /// * `destination` - Bitcoin address to send to
     /// * `amount` - either `AmountOrAll::Amount(n)` for a given amount in satoshi units, or
-    /// `AmountOrAll::All` to spend all available funds
+    ///   `AmountOrAll::All` to spend all available funds
     /// * `feerate` - optional feerate to use for Bitcoin transaction
     #[deprecated(
         since = "0.1.0",
@@ -555,7 +1241,7 @@ impl LightningRPC {
         feerate: Option<u64>,
         minconf: Option<u32>,
     ) -> Result<responses::Withdraw, Error> {
-        self.call(
+        self.call_once(
             "withdraw",
             requests::Withdraw {
                 destination,
@@ -581,20 +1267,2254 @@ impl LightningRPC {
         note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
     )]
     pub fn stop(&self) -> Result<responses::Stop, Error> {
-        self.call("stop", requests::Stop {})
+        self.call_once("stop", requests::Stop {})
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn set_timeout() {
-        use crate::LightningRPC;
-        use std::time::Duration;
+    /// Create a BOLT12 offer for {amount_msat} (or any amount, if no {amount_msat})
+    /// with {description}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn offer(
+        &self,
+        amount_msat: Option<u64>,
+        description: Option<&str>,
+        options: OfferOptions,
+    ) -> Result<responses::Offer, Error> {
+        self.call(
+            "offer",
+            requests::Offer {
+                amount: amount_msat
+                    .map_or(requests::AmountOrAny::Any, requests::AmountOrAny::Amount),
+                description,
+                issuer: options.issuer,
+                label: options.label,
+                quantity_max: options.quantity_max,
+                absolute_expiry: options.absolute_expiry,
+                recurrence: options.recurrence,
+                recurrence_base: options.recurrence_base,
+                recurrence_paywindow: options.recurrence_paywindow,
+                recurrence_limit: options.recurrence_limit,
+                single_use: options.single_use,
+            },
+        )
+    }
 
-        let mut lightning = LightningRPC::new("/test");
-        lightning
-            .client()
-            .set_timeout(Some(Duration::from_millis(100)));
+    /// Show offer {offer_id} (or all, if no {offer_id}), only the active ones if {active_only}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listoffers(
+        &self,
+        offer_id: Option<&str>,
+        active_only: Option<bool>,
+    ) -> Result<responses::ListOffers, Error> {
+        self.call(
+            "listoffers",
+            requests::ListOffers {
+                offer_id,
+                active_only,
+            },
+        )
+    }
+
+    /// Disable offer {offer_id}, so no further invoices will be given out.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn disableoffer(&self, offer_id: &str) -> Result<responses::DisableOffer, Error> {
+        self.call("disableoffer", requests::DisableOffer { offer_id })
+    }
+
+    /// Enable offer {offer_id} again, after it was disabled.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn enableoffer(&self, offer_id: &str) -> Result<responses::EnableOffer, Error> {
+        self.call("enableoffer", requests::EnableOffer { offer_id })
+    }
+
+    /// Request an invoice for the BOLT12 {offer} from its issuer.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn fetchinvoice(
+        &self,
+        offer: &str,
+        options: FetchInvoiceOptions,
+    ) -> Result<responses::FetchInvoice, Error> {
+        self.call(
+            "fetchinvoice",
+            requests::FetchInvoice {
+                offer,
+                amount_msat: options.amount_msat,
+                quantity: options.quantity,
+                recurrence_counter: options.recurrence_counter,
+                recurrence_start: options.recurrence_start,
+                recurrence_label: options.recurrence_label,
+                timeout: options.timeout,
+                payer_note: options.payer_note,
+            },
+        )
+    }
+
+    /// Send an invoice with {label} in response to the BOLT12 invoice request {invreq},
+    /// and wait until it is paid or {timeout} seconds (default 90) elapsed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn sendinvoice(
+        &self,
+        invreq: &str,
+        label: &str,
+        amount_msat: Option<u64>,
+        timeout: Option<u64>,
+        quantity: Option<u64>,
+    ) -> Result<responses::SendInvoice, Error> {
+        self.call(
+            "sendinvoice",
+            requests::SendInvoice {
+                invreq,
+                label,
+                amount_msat,
+                timeout,
+                quantity,
+            },
+        )
+    }
+
+    /// Create a BOLT12 invoice request to send {amount_msat} with {description},
+    /// which the recipient answers with an invoice (a refund or an ATM withdrawal).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn invoicerequest(
+        &self,
+        amount_msat: u64,
+        description: &str,
+        options: InvoiceRequestOptions,
+    ) -> Result<responses::InvoiceRequest, Error> {
+        self.call(
+            "invoicerequest",
+            requests::InvoiceRequest {
+                amount: amount_msat,
+                description,
+                issuer: options.issuer,
+                label: options.label,
+                absolute_expiry: options.absolute_expiry,
+                single_use: options.single_use,
+            },
+        )
+    }
+
+    /// Show invoice request {invreq_id} (or all, if no {invreq_id}), only the active
+    /// ones if {active_only}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listinvoicerequests(
+        &self,
+        invreq_id: Option<&str>,
+        active_only: Option<bool>,
+    ) -> Result<responses::ListInvoiceRequests, Error> {
+        self.call(
+            "listinvoicerequests",
+            requests::ListInvoiceRequests {
+                invreq_id,
+                active_only,
+            },
+        )
+    }
+
+    /// Disable invoice request {invreq_id}, so no further invoices will be accepted.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn disableinvoicerequest(
+        &self,
+        invreq_id: &str,
+    ) -> Result<responses::DisableInvoiceRequest, Error> {
+        self.call(
+            "disableinvoicerequest",
+            requests::DisableInvoiceRequest { invreq_id },
+        )
+    }
+
+    /// Create a PSBT spending {satoshi} (or all) of the wallet funds at {feerate}, for a
+    /// transaction with {startweight} weight before the inputs are added.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn fundpsbt(
+        &self,
+        satoshi: requests::AmountOrAll,
+        feerate: &str,
+        startweight: u32,
+        options: FundPsbtOptions,
+    ) -> Result<responses::FundPsbt, Error> {
+        self.call_once(
+            "fundpsbt",
+            requests::FundPsbt {
+                satoshi,
+                feerate,
+                startweight,
+                minconf: options.minconf,
+                reserve: options.reserve,
+                locktime: options.locktime,
+                min_witness_weight: options.min_witness_weight,
+                excess_as_change: options.excess_as_change,
+                nonwrapped: options.nonwrapped,
+                opening_anchor_channel: options.opening_anchor_channel,
+            },
+        )
+    }
+
+    /// Create a PSBT spending {satoshi} (or all) from the given {utxos} (`txid:vout`) at
+    /// {feerate}, for a transaction with {startweight} weight before the inputs are added.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn utxopsbt(
+        &self,
+        satoshi: requests::AmountOrAll,
+        feerate: &str,
+        startweight: u32,
+        utxos: &[&str],
+        options: UtxoPsbtOptions,
+    ) -> Result<responses::UtxoPsbt, Error> {
+        self.call_once(
+            "utxopsbt",
+            requests::UtxoPsbt {
+                satoshi,
+                feerate,
+                startweight,
+                utxos,
+                reserve: options.reserve,
+                reservedok: options.reservedok,
+                locktime: options.locktime,
+                min_witness_weight: options.min_witness_weight,
+                excess_as_change: options.excess_as_change,
+                opening_anchor_channel: options.opening_anchor_channel,
+            },
+        )
+    }
+
+    /// Reserve the inputs of {psbt} for {reserve} blocks (default 72), failing if any is
+    /// already reserved and {exclusive} (default true).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn reserveinputs(
+        &self,
+        psbt: &Psbt,
+        exclusive: Option<bool>,
+        reserve: Option<u32>,
+    ) -> Result<responses::ReserveInputs, Error> {
+        self.call_once(
+            "reserveinputs",
+            requests::ReserveInputs {
+                psbt,
+                exclusive,
+                reserve,
+            },
+        )
+    }
+
+    /// Release the reservation of the inputs of {psbt} by {reserve} blocks (default 72).
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn unreserveinputs(
+        &self,
+        psbt: &Psbt,
+        reserve: Option<u32>,
+    ) -> Result<responses::UnreserveInputs, Error> {
+        self.call_once(
+            "unreserveinputs",
+            requests::UnreserveInputs { psbt, reserve },
+        )
+    }
+
+    /// Sign the wallet inputs of {psbt}, only the inputs at the indexes {signonly} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn signpsbt(
+        &self,
+        psbt: &Psbt,
+        signonly: Option<&[u32]>,
+    ) -> Result<responses::SignPsbt, Error> {
+        self.call("signpsbt", requests::SignPsbt { psbt, signonly })
+    }
+
+    /// Finalize, extract and broadcast the fully signed {psbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn sendpsbt(
+        &self,
+        psbt: &Psbt,
+        reserve: Option<u32>,
+    ) -> Result<responses::SendPsbt, Error> {
+        self.call_once("sendpsbt", requests::SendPsbt { psbt, reserve })
+    }
+
+    /// Prepare a transaction paying the {outputs}, reserving the used wallet outputs
+    /// until it is sent with txsend() or discarded with txdiscard().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn txprepare(
+        &self,
+        outputs: &[requests::TxOutput],
+        feerate: Option<&str>,
+        minconf: Option<u32>,
+        utxos: Option<&[&str]>,
+    ) -> Result<responses::TxPrepare, Error> {
+        self.call_once(
+            "txprepare",
+            requests::TxPrepare {
+                outputs,
+                feerate,
+                minconf,
+                utxos,
+            },
+        )
+    }
+
+    /// Sign and broadcast the transaction {txid} created by txprepare().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn txsend(&self, txid: &str) -> Result<responses::TxSend, Error> {
+        self.call_once("txsend", requests::TxSend { txid })
+    }
+
+    /// Abandon the transaction {txid} created by txprepare(), releasing its inputs.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn txdiscard(&self, txid: &str) -> Result<responses::TxDiscard, Error> {
+        self.call("txdiscard", requests::TxDiscard { txid })
+    }
+
+    /// Show the transactions tracked by the wallet.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listtransactions(&self) -> Result<responses::ListTransactions, Error> {
+        self.call("listtransactions", requests::ListTransactions {})
+    }
+
+    /// Show the addresses issued by the wallet, only {address} if given, {limit} of them
+    /// starting from the key index {start} otherwise.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listaddresses(
+        &self,
+        address: Option<&str>,
+        start: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<responses::ListAddresses, Error> {
+        self.call(
+            "listaddresses",
+            requests::ListAddresses {
+                address,
+                start,
+                limit,
+            },
+        )
+    }
+
+    /// Send the wallet funds to several {outputs} in a single transaction.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn multiwithdraw(
+        &self,
+        outputs: &[requests::TxOutput],
+        feerate: Option<&str>,
+        minconf: Option<u32>,
+        utxos: Option<&[&str]>,
+    ) -> Result<responses::MultiWithdraw, Error> {
+        self.call_once(
+            "multiwithdraw",
+            requests::MultiWithdraw {
+                outputs,
+                feerate,
+                minconf,
+                utxos,
+            },
+        )
+    }
+
+    /// Open channels with all the {destinations} in a single funding transaction.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn multifundchannel(
+        &self,
+        destinations: &[requests::MultiFundDestination],
+        options: MultiFundChannelOptions,
+    ) -> Result<responses::MultiFundChannel, Error> {
+        self.call_once(
+            "multifundchannel",
+            requests::MultiFundChannel {
+                destinations,
+                feerate: options.feerate,
+                minconf: options.minconf,
+                utxos: options.utxos.as_deref(),
+                minchannels: options.minchannels,
+                commitment_feerate: options.commitment_feerate,
+            },
+        )
+    }
+
+    /// Start opening a channel of {amount} satoshi with {id}, returning the address the
+    /// funding transaction must pay to.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn fundchannel_start(
+        &self,
+        id: &str,
+        amount: u64,
+        options: FundChannelStartOptions,
+    ) -> Result<responses::FundChannelStart, Error> {
+        self.call_once(
+            "fundchannel_start",
+            requests::FundChannelStart {
+                id,
+                amount,
+                feerate: options.feerate,
+                announce: options.announce,
+                close_to: options.close_to,
+                push_msat: options.push_msat,
+                mindepth: options.mindepth,
+                reserve: options.reserve,
+                channel_type: options.channel_type.as_deref(),
+            },
+        )
+    }
+
+    /// Complete the channel opening with {id} started by fundchannel_start(), with the
+    /// funding transaction {psbt}, which can be broadcast once the commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn fundchannel_complete(
+        &self,
+        id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::FundChannelComplete, Error> {
+        self.call_once(
+            "fundchannel_complete",
+            requests::FundChannelComplete { id, psbt },
+        )
+    }
+
+    /// Cancel the channel opening with {id} started by fundchannel_start().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn fundchannel_cancel(&self, id: &str) -> Result<responses::FundChannelCancel, Error> {
+        self.call("fundchannel_cancel", requests::FundChannelCancel { id })
+    }
+
+    /// Start opening a dual-funded channel with {id}, contributing {amount} satoshi from
+    /// the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn openchannel_init(
+        &self,
+        id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        options: OpenChannelOptions,
+    ) -> Result<responses::OpenChannelInit, Error> {
+        self.call_once(
+            "openchannel_init",
+            requests::OpenChannelInit {
+                id,
+                amount,
+                initialpsbt,
+                commitment_feerate: options.commitment_feerate,
+                funding_feerate: options.funding_feerate,
+                announce: options.announce,
+                close_to: options.close_to,
+                request_amt: options.request_amt,
+                compact_lease: options.compact_lease,
+                channel_type: options.channel_type.as_deref(),
+            },
+        )
+    }
+
+    /// Exchange the updated {psbt} of the dual-funded opening of {channel_id} with the peer,
+    /// until the commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn openchannel_update(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::OpenChannelUpdate, Error> {
+        self.call_once(
+            "openchannel_update",
+            requests::OpenChannelUpdate { channel_id, psbt },
+        )
+    }
+
+    /// Send the {signed_psbt} of the dual-funded opening of {channel_id} to the peer, the
+    /// funding transaction is broadcast once both sides signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn openchannel_signed(
+        &self,
+        channel_id: &str,
+        signed_psbt: &Psbt,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        self.call_once(
+            "openchannel_signed",
+            requests::OpenChannelSigned {
+                channel_id,
+                signed_psbt,
+            },
+        )
+    }
+
+    /// Replace the funding transaction of the pending dual-funded channel {channel_id}
+    /// (RBF), contributing {amount} satoshi from the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn openchannel_bump(
+        &self,
+        channel_id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        funding_feerate: Option<&str>,
+    ) -> Result<responses::OpenChannelBump, Error> {
+        self.call_once(
+            "openchannel_bump",
+            requests::OpenChannelBump {
+                channel_id,
+                amount,
+                initialpsbt,
+                funding_feerate,
+            },
+        )
+    }
+
+    /// Abort the dual-funded opening of {channel_id}, before the funding is signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn openchannel_abort(
+        &self,
+        channel_id: &str,
+    ) -> Result<responses::OpenChannelAbort, Error> {
+        self.call(
+            "openchannel_abort",
+            requests::OpenChannelAbort { channel_id },
+        )
+    }
+
+    /// Start splicing {relative_amount} satoshi into (positive) or out of (negative)
+    /// {channel_id}, funding a splice-in from the inputs of {initialpsbt}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn splice_init(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+        force_feerate: Option<bool>,
+    ) -> Result<responses::SpliceInit, Error> {
+        self.call_once(
+            "splice_init",
+            requests::SpliceInit {
+                channel_id,
+                relative_amount,
+                initialpsbt,
+                feerate_per_kw,
+                force_feerate,
+            },
+        )
+    }
+
+    /// Exchange the updated {psbt} of the splice of {channel_id} with the peer, until the
+    /// commitments are secured.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn splice_update(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+    ) -> Result<responses::SpliceUpdate, Error> {
+        self.call_once("splice_update", requests::SpliceUpdate { channel_id, psbt })
+    }
+
+    /// Send the signed {psbt} of the splice of {channel_id} to the peer, the splice
+    /// transaction is broadcast once both sides signed.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn splice_signed(
+        &self,
+        channel_id: &str,
+        psbt: &Psbt,
+        sign_first: Option<bool>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        self.call_once(
+            "splice_signed",
+            requests::SpliceSigned {
+                channel_id,
+                psbt,
+                sign_first,
+            },
+        )
+    }
+
+    /// Open a dual-funded channel with {id}, contributing {amount} satoshi from the inputs
+    /// of {initialpsbt} (e.g. made by fundpsbt()).
+    ///
+    /// The PSBT is exchanged with openchannel_update() until the commitments are secured,
+    /// then its wallet inputs are signed and the funding transaction is broadcast. If a step
+    /// fails, or the commitments are not secured after `MAX_UPDATE_ROUNDS` updates
+    /// (`Error::CommitmentsNotSecured`), the opening is aborted and the inputs of
+    /// {initialpsbt} are unreserved.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    #[allow(deprecated)]
+    pub fn open_dual_funded_channel(
+        &self,
+        id: &str,
+        amount: u64,
+        initialpsbt: &Psbt,
+        options: OpenChannelOptions,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        let result = self
+            .openchannel_init(id, amount, initialpsbt, options)
+            .and_then(|init| {
+                let result =
+                    self.finish_openchannel(&init.channel_id, init.psbt, init.commitments_secured);
+                if result.is_err() {
+                    // best effort, the opening times out on the peer side anyway
+                    let _ = self.openchannel_abort(&init.channel_id);
+                }
+                result
+            });
+        if result.is_err() {
+            // best effort, the reservation expires after 72 blocks anyway
+            let _ = self.unreserveinputs(initialpsbt, None);
+        }
+        result
+    }
+
+    #[allow(deprecated)]
+    fn finish_openchannel(
+        &self,
+        channel_id: &str,
+        mut psbt: Psbt,
+        mut commitments_secured: bool,
+    ) -> Result<responses::OpenChannelSigned, Error> {
+        let mut rounds = 0;
+        while !commitments_secured {
+            if rounds == MAX_UPDATE_ROUNDS {
+                return Err(Error::CommitmentsNotSecured);
+            }
+            rounds += 1;
+            let update = self.openchannel_update(channel_id, &psbt)?;
+            psbt = update.psbt;
+            commitments_secured = update.commitments_secured;
+        }
+        let signed = self.signpsbt(&psbt, None)?;
+        self.openchannel_signed(channel_id, &signed.signed_psbt)
+    }
+
+    /// Splice {relative_amount} satoshi into (positive) or out of (negative) {channel_id},
+    /// funding a splice-in from the inputs of {initialpsbt} (e.g. made by fundpsbt()).
+    ///
+    /// The PSBT is exchanged with splice_update() until the commitments are secured, then
+    /// the wallet inputs of {initialpsbt} are signed and the splice transaction is broadcast.
+    /// If a step fails, or the commitments are not secured after `MAX_UPDATE_ROUNDS` updates
+    /// (`Error::CommitmentsNotSecured`), the inputs of {initialpsbt} are unreserved.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    #[allow(deprecated)]
+    pub fn splice_channel(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        let result = self.finish_splice(channel_id, relative_amount, initialpsbt, feerate_per_kw);
+        if let (Err(_), Some(initialpsbt)) = (&result, initialpsbt) {
+            // best effort, the reservation expires after 72 blocks anyway
+            let _ = self.unreserveinputs(initialpsbt, None);
+        }
+        result
+    }
+
+    #[allow(deprecated)]
+    fn finish_splice(
+        &self,
+        channel_id: &str,
+        relative_amount: i64,
+        initialpsbt: Option<&Psbt>,
+        feerate_per_kw: Option<u32>,
+    ) -> Result<responses::SpliceSigned, Error> {
+        let init = self.splice_init(
+            channel_id,
+            relative_amount,
+            initialpsbt,
+            feerate_per_kw,
+            None,
+        )?;
+        let mut psbt = init.psbt;
+        for _ in 0..MAX_UPDATE_ROUNDS {
+            let update = self.splice_update(channel_id, &psbt)?;
+            psbt = update.psbt;
+            if update.commitments_secured {
+                if initialpsbt.is_some() {
+                    psbt = self.signpsbt(&psbt, None)?.signed_psbt;
+                }
+                return self.splice_signed(channel_id, &psbt, None);
+            }
+        }
+        Err(Error::CommitmentsNotSecured)
+    }
+
+    /// Send the custom {message} to the connected peer {node_id}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn sendcustommsg(
+        &self,
+        node_id: &str,
+        message: &CustomMessage,
+    ) -> Result<responses::SendCustomMsg, Error> {
+        self.call_once(
+            "sendcustommsg",
+            requests::SendCustomMsg {
+                node_id,
+                msg: message.to_hex(),
+            },
+        )
+    }
+
+    /// Send an onion message along the blinded path {hops}, through the connected peer
+    /// {first_id}, with the blinding point {path_key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn sendonionmessage(
+        &self,
+        first_id: &str,
+        path_key: &str,
+        hops: &[requests::OnionMessageHop],
+    ) -> Result<responses::SendOnionMessage, Error> {
+        self.call_once(
+            "sendonionmessage",
+            requests::SendOnionMessage {
+                first_id,
+                path_key,
+                hops,
+            },
+        )
+    }
+
+    /// Process the onion {message} as if it was received from a peer, with the blinding
+    /// point {path_key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn injectonionmessage(
+        &self,
+        path_key: &str,
+        message: &Hex,
+    ) -> Result<responses::InjectOnionMessage, Error> {
+        self.call_once(
+            "injectonionmessage",
+            requests::InjectOnionMessage { path_key, message },
+        )
+    }
+
+    /// Sign {message} with the node key, to prove the node ownership.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn signmessage(&self, message: &str) -> Result<responses::SignMessage, Error> {
+        self.call("signmessage", requests::SignMessage { message })
+    }
+
+    /// Check the {zbase} signature of {message}, made by {pubkey} if given. Without {pubkey},
+    /// the signature is only verified if it was made by a node known from the gossip.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn checkmessage(
+        &self,
+        message: &str,
+        zbase: &str,
+        pubkey: Option<&str>,
+    ) -> Result<responses::CheckMessage, Error> {
+        self.call(
+            "checkmessage",
+            requests::CheckMessage {
+                message,
+                zbase,
+                pubkey,
+            },
+        )
+    }
+
+    /// Sign the BOLT11 {invstring} with the node key.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn signinvoice(&self, invstring: &str) -> Result<responses::SignInvoice, Error> {
+        self.call("signinvoice", requests::SignInvoice { invstring })
+    }
+
+    /// Derive a secret from the node secret and either {hex} or {string}, which must be unique
+    /// to the service using it.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn makesecret(
+        &self,
+        hex: Option<&str>,
+        string: Option<&str>,
+    ) -> Result<responses::MakeSecret, Error> {
+        self.call("makesecret", requests::MakeSecret { hex, string })
+    }
+
+    /// Return the encrypted emergency.recover data, to back up the channels of the node.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn getemergencyrecoverdata(&self) -> Result<responses::GetEmergencyRecoverData, Error> {
+        self.call(
+            "getemergencyrecoverdata",
+            requests::GetEmergencyRecoverData {},
+        )
+    }
+
+    /// Recover the channels from the emergency.recover file, asking the peers to close them.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn emergencyrecover(&self) -> Result<responses::EmergencyRecover, Error> {
+        self.call("emergencyrecover", requests::EmergencyRecover {})
+    }
+
+    /// Recover the channels from the static channel backups {scb}, asking the peers to close
+    /// them.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn recoverchannel(&self, scb: &[Hex]) -> Result<responses::RecoverChannel, Error> {
+        self.call("recoverchannel", requests::RecoverChannel { scb })
+    }
+
+    /// Return the static channel backups of the channels, see recoverchannel().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn staticbackup(&self) -> Result<responses::StaticBackup, Error> {
+        self.call("staticbackup", requests::StaticBackup {})
+    }
+
+    /// Create a new rune with the {restrictions} (see `crate::rune::Restriction`), or
+    /// add them to the existing {rune}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn createrune(
+        &self,
+        rune: Option<&str>,
+        restrictions: Option<&[Restriction]>,
+    ) -> Result<responses::CreateRune, Error> {
+        self.call(
+            "createrune",
+            requests::CreateRune {
+                rune,
+                restrictions: restrictions
+                    .map(|restrictions| restrictions.iter().map(Restriction::to_strings).collect()),
+            },
+        )
+    }
+
+    /// Show {rune} decoded, or all the runes created by this node if no {rune}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn showrunes(&self, rune: Option<&str>) -> Result<responses::ShowRunes, Error> {
+        self.call("showrunes", requests::ShowRunes { rune })
+    }
+
+    /// Blacklist the runes with unique ids from {start} to {end} (default {start}), or
+    /// remove them from the blacklist if {relist}. Only shows the blacklist without {start}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn blacklistrune(
+        &self,
+        start: Option<u64>,
+        end: Option<u64>,
+        relist: Option<bool>,
+    ) -> Result<responses::BlacklistRune, Error> {
+        self.call(
+            "blacklistrune",
+            requests::BlacklistRune { start, end, relist },
+        )
+    }
+
+    /// Check whether {rune} allows the node {nodeid} to run {method} with {params}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn checkrune(
+        &self,
+        rune: &str,
+        nodeid: Option<&str>,
+        method: Option<&str>,
+        params: Option<&Value>,
+    ) -> Result<responses::CheckRune, Error> {
+        self.call(
+            "checkrune",
+            requests::CheckRune {
+                rune,
+                nodeid,
+                method,
+                params,
+            },
+        )
+    }
+
+    /// Store {data} under the hierarchical {key}, according to {mode} (default must-create).
+    /// With {generation}, the entry is only changed if it is still at that generation.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn datastore<K: AsRef<str>>(
+        &self,
+        key: &[K],
+        data: requests::DatastoreData,
+        mode: Option<requests::DatastoreMode>,
+        generation: Option<u64>,
+    ) -> Result<responses::Datastore, Error> {
+        let (string, hex) = match data {
+            requests::DatastoreData::String(string) => (Some(string), None),
+            requests::DatastoreData::Hex(hex) => (None, Some(hex)),
+        };
+        self.call_once(
+            "datastore",
+            requests::Datastore {
+                key: key.iter().map(AsRef::as_ref).collect(),
+                string,
+                hex,
+                mode,
+                generation,
+            },
+        )
+    }
+
+    /// Remove the entry {key} from the datastore, only if it is still at {generation} if given.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn deldatastore<K: AsRef<str>>(
+        &self,
+        key: &[K],
+        generation: Option<u64>,
+    ) -> Result<responses::DelDatastore, Error> {
+        self.call(
+            "deldatastore",
+            requests::DelDatastore {
+                key: key.iter().map(AsRef::as_ref).collect(),
+                generation,
+            },
+        )
+    }
+
+    /// Show the datastore entry {key} and its children, or the top-level entries if no {key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn listdatastore<K: AsRef<str>>(
+        &self,
+        key: Option<&[K]>,
+    ) -> Result<responses::ListDatastore, Error> {
+        self.call(
+            "listdatastore",
+            requests::ListDatastore {
+                key: key.map(|key| key.iter().map(AsRef::as_ref).collect()),
+            },
+        )
+    }
+
+    /// Show the bytes used by the datastore entry {key} and its children, or by the whole
+    /// datastore if no {key}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn datastoreusage<K: AsRef<str>>(
+        &self,
+        key: Option<&[K]>,
+    ) -> Result<responses::DatastoreUsage, Error> {
+        self.call(
+            "datastoreusage",
+            requests::DatastoreUsage {
+                key: key.map(|key| key.iter().map(AsRef::as_ref).collect()),
+            },
+        )
+    }
+
+    /// Find routes paying {amount_msat} from {source} to {destination}, possibly split in
+    /// several parts, using the gossip amended by the askrene {layers}. The fees of all
+    /// the parts together stay below {maxfee_msat}, and {final_cltv} is the delay
+    /// required by the destination.
+    #[allow(clippy::too_many_arguments)]
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn getroutes(
+        &self,
+        source: &str,
+        destination: &str,
+        amount_msat: u64,
+        layers: &[&str],
+        maxfee_msat: u64,
+        final_cltv: u32,
+        options: GetRoutesOptions,
+    ) -> Result<responses::GetRoutes, Error> {
+        self.call(
+            "getroutes",
+            requests::GetRoutes {
+                source,
+                destination,
+                amount_msat,
+                layers,
+                maxfee_msat,
+                final_cltv,
+                maxdelay: options.maxdelay,
+                maxparts: options.maxparts,
+            },
+        )
+    }
+
+    /// Create the askrene layer {layer}, kept across restarts if {persistent}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_create_layer(
+        &self,
+        layer: &str,
+        persistent: Option<bool>,
+    ) -> Result<responses::AskreneCreateLayer, Error> {
+        self.call(
+            "askrene-create-layer",
+            requests::AskreneCreateLayer { layer, persistent },
+        )
+    }
+
+    /// Record in {layer} what was learnt about the channel direction {short_channel_id_dir}
+    /// (e.g. '103x1x0/1') while sending {amount_msat} through it, {inform} telling
+    /// whether the payment failed (constrained) or went through it.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_inform_channel(
+        &self,
+        layer: &str,
+        short_channel_id_dir: &str,
+        amount_msat: Option<u64>,
+        inform: Option<AskreneInform>,
+    ) -> Result<responses::AskreneInformChannel, Error> {
+        self.call(
+            "askrene-inform-channel",
+            requests::AskreneInformChannel {
+                layer,
+                short_channel_id_dir,
+                amount_msat,
+                inform,
+            },
+        )
+    }
+
+    /// Override in {layer} the gossip of the channel direction {short_channel_id_dir},
+    /// see `AskreneUpdateChannelOptions` for the fields.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_update_channel(
+        &self,
+        layer: &str,
+        short_channel_id_dir: &str,
+        options: AskreneUpdateChannelOptions,
+    ) -> Result<responses::AskreneUpdateChannel, Error> {
+        self.call(
+            "askrene-update-channel",
+            requests::AskreneUpdateChannel {
+                layer,
+                short_channel_id_dir,
+                enabled: options.enabled,
+                htlc_minimum_msat: options.htlc_minimum_msat,
+                htlc_maximum_msat: options.htlc_maximum_msat,
+                fee_base_msat: options.fee_base_msat,
+                fee_proportional_millionths: options.fee_proportional_millionths,
+                cltv_expiry_delta: options.cltv_expiry_delta,
+            },
+        )
+    }
+
+    /// Add to {layer} a channel {short_channel_id} of {capacity_msat} from {source} to
+    /// {destination} which is not in the gossip (e.g. a private channel), its fees must
+    /// then be set with askrene_update_channel().
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_create_channel(
+        &self,
+        layer: &str,
+        source: &str,
+        destination: &str,
+        short_channel_id: &str,
+        capacity_msat: u64,
+    ) -> Result<responses::AskreneCreateChannel, Error> {
+        self.call(
+            "askrene-create-channel",
+            requests::AskreneCreateChannel {
+                layer,
+                source,
+                destination,
+                short_channel_id,
+                capacity_msat,
+            },
+        )
+    }
+
+    /// Show the askrene layer {layer}, or all the layers if no {layer}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_listlayers(
+        &self,
+        layer: Option<&str>,
+    ) -> Result<responses::AskreneListLayers, Error> {
+        self.call("askrene-listlayers", requests::AskreneListLayers { layer })
+    }
+
+    /// Remove from {layer} the channel constraints recorded before the UNIX time {cutoff}.
+    #[deprecated(
+        since = "0.1.0",
+        note = "Core Lightning API changes frequently, making strongly typed methods hard to maintain. Use the generic `call` method with serde_json until a compiler is shipped or the API stabilizes."
+    )]
+    pub fn askrene_age(&self, layer: &str, cutoff: u64) -> Result<responses::AskreneAge, Error> {
+        self.call("askrene-age", requests::AskreneAge { layer, cutoff })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use clightningrpc_common::errors::Error;
+    use clightningrpc_common::transport::Mock;
+    use serde::Serialize;
+    use serde_json::Value;
+
+    use super::LightningRPC;
+
+    /// Answer the call made by `call` with `response`, and check that it sent exactly
+    /// one `method` request with `params` and that every field of `response` made it
+    /// into the decoded response, which is returned.
+    pub(crate) fn check_call<T, F>(method: &str, params: Value, response: Value, call: F) -> T
+    where
+        T: Serialize,
+        F: FnOnce(&LightningRPC<Mock>) -> Result<T, Error>,
+    {
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning.client.expect(method, response.clone());
+        let decoded = call(&lightning).unwrap();
+        assert_eq!(lightning.client.requests(), [(method.to_owned(), params)]);
+        assert_decoded("", &response, &serde_json::to_value(&decoded).unwrap());
+        decoded
+    }
+
+    /// Check that all the fields of the `expected` JSON are in `decoded`
+    fn assert_decoded(path: &str, expected: &Value, decoded: &Value) {
+        match (expected, decoded) {
+            (Value::Object(expected), Value::Object(decoded)) => {
+                for (key, value) in expected {
+                    let field = decoded.get(key).unwrap_or(&Value::Null);
+                    assert_decoded(&format!("{path}.{key}"), value, field);
+                }
+            }
+            (Value::Array(expected), Value::Array(decoded)) => {
+                assert_eq!(expected.len(), decoded.len(), "length of {path}");
+                for (i, (value, item)) in expected.iter().zip(decoded).enumerate() {
+                    assert_decoded(&format!("{path}[{i}]"), value, item);
+                }
+            }
+            (Value::Number(expected), Value::Number(decoded)) => {
+                assert_eq!(expected.as_f64(), decoded.as_f64(), "{path}")
+            }
+            (expected, decoded) => assert_eq!(expected, decoded, "{path}"),
+        }
+    }
+
+    #[test]
+    fn derive_response_filter() {
+        use clightningrpc_common::filter::filter_for;
+        use serde_json::json;
+
+        use crate::responses;
+
+        let filter = filter_for::<responses::ListChannels>().unwrap();
+        assert_eq!(filter["channels"][0]["short_channel_id"], json!(true));
+        assert_eq!(filter["channels"][0]["amount_msat"], json!(true));
+        let filter = filter_for::<responses::GetInfo>().unwrap();
+        assert_eq!(filter["lightning-dir"], json!(true));
+        assert_eq!(filter["address"], json!([true]));
+    }
+
+    #[test]
+    fn set_timeout() {
+        use crate::LightningRPC;
+        use std::time::Duration;
+
+        let mut lightning = LightningRPC::new("/test");
+        lightning
+            .client()
+            .set_timeout(Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn mock_transport() {
+        use clightningrpc_common::transport::Mock;
+        use serde_json::{json, Value};
+
+        use super::LightningRPC;
+
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning
+            .client
+            .expect("listfunds", json!({ "outputs": [] }));
+        let funds: Value = lightning.call("listfunds", json!({})).unwrap();
+        assert_eq!(funds, json!({ "outputs": [] }));
+        assert!(lightning.call::<_, Value>("listfunds", json!({})).is_err());
+        assert_eq!(
+            lightning.client.requests(),
+            vec![
+                ("listfunds".to_owned(), json!({})),
+                ("listfunds".to_owned(), json!({}))
+            ]
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn listpeerchannels() {
+        use serde_json::json;
+
+        let channels = check_call(
+            "listpeerchannels",
+            json!({ "id": "0266e4598d1d3c415f572a8488830b60f7e744ed9235eb0b1ba93283b315c03518" }),
+            json!({
+                "channels": [{
+                    "peer_id": "0266e4598d1d3c415f572a8488830b60f7e744ed9235eb0b1ba93283b315c03518",
+                    "peer_connected": true,
+                    "state": "CHANNELD_NORMAL",
+                    "short_channel_id": "103x1x0",
+                    "direction": 1,
+                    "channel_id": "c2e9b2b4a8c1e9b0ed0f5e3f4b8f4f9d1e0c3f6a7b8c9d0e1f2a3b4c5d6e7f80",
+                    "funding_txid": "80f7e6d5c4b3a2f1e0d9c8b7a6f3c0e1d9f4f8b4f3e5f0edb0e9c1a8b4b2e9c2",
+                    "funding_outnum": 0,
+                    "opener": "local",
+                    "private": false,
+                    "features": ["option_static_remotekey"],
+                    "funding": {"local_funds_msat": 1000000000, "remote_funds_msat": 0},
+                    "to_us_msat": 1000000000,
+                    "total_msat": 1000000000,
+                    "alias": {"local": "6128x1x0", "remote": "4294x3x1"},
+                    "updates": {
+                        "local": {
+                            "htlc_minimum_msat": 0,
+                            "htlc_maximum_msat": 990000000,
+                            "cltv_expiry_delta": 6,
+                            "fee_base_msat": 1,
+                            "fee_proportional_millionths": 10
+                        }
+                    },
+                    "htlcs": [{
+                        "direction": "out",
+                        "id": 0,
+                        "amount_msat": 1000,
+                        "expiry": 120,
+                        "payment_hash": "00",
+                        "state": "SENT_ADD_HTLC"
+                    }]
+                }]
+            }),
+            |lightning| {
+                lightning.listpeerchannels(Some(
+                    "0266e4598d1d3c415f572a8488830b60f7e744ed9235eb0b1ba93283b315c03518",
+                ))
+            },
+        )
+        .channels;
+        let channel = &channels[0];
+        assert_eq!(channel.short_channel_id.as_deref(), Some("103x1x0"));
+        let alias = channel.alias.as_ref().unwrap();
+        assert_eq!(alias.remote.as_deref(), Some("4294x3x1"));
+        let updates = channel.updates.as_ref().unwrap();
+        assert_eq!(updates.local.cltv_expiry_delta, 6);
+        assert!(updates.remote.is_none());
+        assert_eq!(channel.htlcs.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn offers_and_invoice_requests() {
+        use serde_json::json;
+
+        use super::{FetchInvoiceOptions, InvoiceRequestOptions, OfferOptions};
+        use crate::types::MSat;
+
+        let offer_id = "b3d8f29b9c3b25fbf4c4a1e2f2b4e7b1f8f0d7c7e2a6a1d1c9b8f5e4d3c2b1a0";
+        let bolt12 = "lno1qgsqvgnwgcg35z6ee2h3yczraddm72xrfua9uve2rlrm9deu7xyfzrcgqyppgyrrmzvwhjefpm7t2ttcetf7e5q";
+        let offer = check_call(
+            "offer",
+            json!({
+                "amount": "10000msat",
+                "description": "coffee",
+                "issuer": "ACME",
+                "label": "coffee-1",
+                "recurrence": "1month",
+                "single_use": false
+            }),
+            json!({
+                "offer_id": offer_id,
+                "active": true,
+                "single_use": false,
+                "bolt12": bolt12,
+                "used": false,
+                "created": true,
+                "label": "coffee-1"
+            }),
+            |lightning| {
+                lightning.offer(
+                    Some(10000),
+                    Some("coffee"),
+                    OfferOptions {
+                        issuer: Some("ACME"),
+                        label: Some("coffee-1"),
+                        recurrence: Some("1month"),
+                        single_use: Some(false),
+                        ..OfferOptions::default()
+                    },
+                )
+            },
+        );
+        assert_eq!(offer.created, Some(true));
+        check_call(
+            "offer",
+            json!({ "amount": "any" }),
+            json!({
+                "offer_id": offer_id,
+                "active": true,
+                "single_use": false,
+                "bolt12": bolt12,
+                "used": false,
+                "created": false
+            }),
+            |lightning| lightning.offer(None, None, OfferOptions::default()),
+        );
+
+        let offers = check_call(
+            "listoffers",
+            json!({ "active_only": true }),
+            json!({ "offers": [
+                {
+                    "offer_id": offer_id,
+                    "active": true,
+                    "single_use": false,
+                    "bolt12": bolt12,
+                    "used": true,
+                    "label": "coffee-1"
+                },
+                {
+                    "offer_id": "0f3c2a1b9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a",
+                    "active": true,
+                    "single_use": true,
+                    "bolt12": "lno1qgsqvgnwgcg35z6ee2h3yczraddm72xrfua9uve2rlrm9deu7xyfzrc",
+                    "used": false
+                }
+            ]}),
+            |lightning| lightning.listoffers(None, Some(true)),
+        )
+        .offers;
+        assert_eq!(offers[0].label.as_deref(), Some("coffee-1"));
+        assert!(offers[1].label.is_none());
+        assert!(offers[1].created.is_none());
+
+        let disabled = check_call(
+            "disableoffer",
+            json!({ "offer_id": offer_id }),
+            json!({
+                "offer_id": offer_id,
+                "active": false,
+                "single_use": false,
+                "bolt12": bolt12,
+                "used": true,
+                "label": "coffee-1"
+            }),
+            |lightning| lightning.disableoffer(offer_id),
+        );
+        assert!(!disabled.active);
+        let enabled = check_call(
+            "enableoffer",
+            json!({ "offer_id": offer_id }),
+            json!({
+                "offer_id": offer_id,
+                "active": true,
+                "single_use": false,
+                "bolt12": bolt12,
+                "used": true,
+                "label": "coffee-1"
+            }),
+            |lightning| lightning.enableoffer(offer_id),
+        );
+        assert!(enabled.active);
+
+        let fetched = check_call(
+            "fetchinvoice",
+            json!({
+                "offer": bolt12,
+                "amount_msat": 12000,
+                "recurrence_counter": 1,
+                "recurrence_label": "coffee-sub",
+                "timeout": 30.0,
+                "payer_note": "thanks"
+            }),
+            json!({
+                "invoice": "lni1qqgqvgnwgcg35z6ee2h3yczraddm72xrfua9uve2rlrm9deu7xyfzrcgqyppgyrrmzvwhjefpm7t2ttcetf7e5qsz",
+                "changes": {
+                    "description_appended": " (extra shot)",
+                    "vendor_removed": "ACME",
+                    "amount_msat": 12000
+                },
+                "next_period": {
+                    "counter": 2,
+                    "starttime": 1702592000,
+                    "endtime": 1705270399,
+                    "paywindow_start": 1702588400,
+                    "paywindow_end": 1705270399
+                }
+            }),
+            |lightning| {
+                lightning.fetchinvoice(
+                    bolt12,
+                    FetchInvoiceOptions {
+                        amount_msat: Some(12000),
+                        recurrence_counter: Some(1),
+                        recurrence_label: Some("coffee-sub"),
+                        timeout: Some(30.0),
+                        payer_note: Some("thanks"),
+                        ..FetchInvoiceOptions::default()
+                    },
+                )
+            },
+        );
+        assert_eq!(fetched.changes.amount_msat, Some(MSat(12000)));
+        assert!(fetched.changes.description.is_none());
+        assert_eq!(fetched.next_period.unwrap().counter, 2);
+        let fetched = check_call(
+            "fetchinvoice",
+            json!({ "offer": bolt12 }),
+            json!({
+                "invoice": "lni1qqgqvgnwgcg35z6ee2h3yczraddm72xrfua9uve2rlrm9deu7xyfzrc",
+                "changes": {}
+            }),
+            |lightning| lightning.fetchinvoice(bolt12, FetchInvoiceOptions::default()),
+        );
+        assert!(fetched.next_period.is_none());
+
+        let invreq_id = "5c7a1e9d3b2f4a6c8e0d1f3b5a7c9e1d3f5b7a9c1e3d5f7b9a1c3e5d7f9b1a3c";
+        let invreq_bolt12 = "lnr1qqgz2d7u2smys9dc5q2447e8thjlgq3qqc3xu3s3rg94nj40zfsy866mhu5vxne6tcej5878k2mneuvgjy8";
+        let invreq = check_call(
+            "invoicerequest",
+            json!({
+                "amount": 50000,
+                "description": "refund",
+                "label": "refund-7",
+                "absolute_expiry": 1700086400
+            }),
+            json!({
+                "invreq_id": invreq_id,
+                "active": true,
+                "single_use": true,
+                "bolt12": invreq_bolt12,
+                "used": false,
+                "label": "refund-7"
+            }),
+            |lightning| {
+                lightning.invoicerequest(
+                    50000,
+                    "refund",
+                    InvoiceRequestOptions {
+                        label: Some("refund-7"),
+                        absolute_expiry: Some(1700086400),
+                        ..InvoiceRequestOptions::default()
+                    },
+                )
+            },
+        );
+        assert!(invreq.single_use);
+        let invreqs = check_call(
+            "listinvoicerequests",
+            json!({ "invreq_id": invreq_id }),
+            json!({ "invoicerequests": [{
+                "invreq_id": invreq_id,
+                "active": true,
+                "single_use": true,
+                "bolt12": invreq_bolt12,
+                "used": false,
+                "label": "refund-7"
+            }]}),
+            |lightning| lightning.listinvoicerequests(Some(invreq_id), None),
+        )
+        .invoicerequests;
+        assert_eq!(invreqs[0].invreq_id, invreq_id);
+        let disabled = check_call(
+            "disableinvoicerequest",
+            json!({ "invreq_id": invreq_id }),
+            json!({
+                "invreq_id": invreq_id,
+                "active": false,
+                "single_use": true,
+                "bolt12": invreq_bolt12,
+                "used": false
+            }),
+            |lightning| lightning.disableinvoicerequest(invreq_id),
+        );
+        assert!(!disabled.active);
+
+        let sent = check_call(
+            "sendinvoice",
+            json!({ "invreq": invreq_bolt12, "label": "withdrawal-3", "timeout": 60 }),
+            json!({
+                "label": "withdrawal-3",
+                "description": "refund",
+                "payment_hash": "f8c7a3b2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4",
+                "status": "paid",
+                "created_index": 12,
+                "updated_index": 4,
+                "expires_at": 1700007200,
+                "amount_msat": 50000,
+                "bolt12": "lni1qqgz2d7u2smys9dc5q2447e8thjlgq3qqc3xu3s3rg94nj40zfsy866mhu5vxne6tcej5878k2mneuvgjy8s",
+                "pay_index": 9,
+                "amount_received_msat": 50000,
+                "paid_at": 1700003600,
+                "payment_preimage": "0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9"
+            }),
+            |lightning| lightning.sendinvoice(invreq_bolt12, "withdrawal-3", None, Some(60), None),
+        );
+        assert_eq!(sent.amount_received_msat, Some(MSat(50000)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn decode_tagged_strings() {
+        use serde_json::json;
+
+        use crate::responses::Decoded;
+
+        let offer = check_call(
+            "decode",
+            json!({ "string": "lno1qgsq" }),
+            json!({
+                "type": "bolt12 offer",
+                "offer_id": "9b8ad7ab0b3e4e4bd2fe4a4fb7c5b0b1f6e4e1c3d2a1b0c9d8e7f6a5b4c3d2e1",
+                "offer_description": "coffee",
+                "offer_amount_msat": 10000,
+                "offer_node_id": "02e2dfbab6ecb8ab6dfd1fb51c8c42fb1d4fa2fd2ed5e0d6a0a3a7a1d3e4f5a6b7",
+                "valid": true
+            }),
+            |lightning| lightning.decode("lno1qgsq"),
+        );
+        match offer {
+            Decoded::Bolt12Offer(offer) => {
+                assert!(offer.valid);
+                assert_eq!(offer.offer.offer_description.as_deref(), Some("coffee"));
+            }
+            other => panic!("unexpected decode {:?}", other),
+        }
+        let rune = check_call(
+            "decode",
+            json!({ "string": "5fG1" }),
+            json!({
+                "type": "rune",
+                "unique_id": "0",
+                "string": "e5f1...:=0&method=getinfo",
+                "restrictions": [{"alternatives": ["method=getinfo"], "summary": "method (of command) equal to 'getinfo'"}],
+                "valid": true
+            }),
+            |lightning| lightning.decode("5fG1"),
+        );
+        match rune {
+            Decoded::Rune(rune) => assert_eq!(rune.restrictions.len(), 1),
+            other => panic!("unexpected decode {:?}", other),
+        }
+        // the fields of an unknown type are dropped, so there is nothing to compare
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning
+            .client
+            .expect("decode", json!({ "type": "future thing", "valid": true }));
+        let unknown = lightning.decode("???").unwrap();
+        assert!(matches!(unknown, Decoded::Unknown));
+        assert!(!unknown.is_valid());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn decode_invoice_request_warnings() {
+        use serde_json::json;
+
+        use crate::responses::Decoded;
+
+        let invreq = check_call(
+            "decode",
+            json!({ "string": "lnr1qqsg" }),
+            json!({
+                "type": "bolt12 invoice_request",
+                "offer_id": "b4bda6e5c1a4bd1ba94bc9f3b5bbd3d3eb6ee1bb8fd0a80fe4c3cf0d9dbc2346",
+                "offer_description": "coffee",
+                "offer_node_id": "02e2dfbab6ecb8ab6dfd1fb51c8c42fb1d4fa2fd2ed5e0d6a0a3a7a1d3e4f5a6b7",
+                "invreq_amount_msat": 10000,
+                "warning_missing_invreq_metadata": "Missing invreq_metadata",
+                "warning_missing_invreq_payer_id": "Missing invreq_payer_id",
+                "warning_invalid_invreq_payer_note": "invreq_payer_note is not valid UTF8",
+                "warning_missing_invoice_request_signature": "Missing signature",
+                "valid": false
+            }),
+            |lightning| lightning.decode("lnr1qqsg"),
+        );
+        match invreq {
+            Decoded::Bolt12InvoiceRequest(invreq) => {
+                assert!(!invreq.valid);
+                assert!(invreq.warning_missing_invoice_request_signature.is_some());
+                assert!(invreq.warning_invalid_invoice_request_signature.is_none());
+                assert!(invreq.invreq.warning_missing_invreq_payer_id.is_some());
+            }
+            other => panic!("unexpected decode {:?}", other),
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn keysend_extratlvs() {
+        use std::collections::BTreeMap;
+
+        use serde_json::json;
+
+        use super::KeysendOptions;
+        use crate::types::{MSat, RouteHintHop};
+
+        let mut extratlvs = BTreeMap::new();
+        extratlvs.insert(133773310, "68656c6c6f".to_owned());
+        let options = KeysendOptions {
+            maxfee: Some(10),
+            routehints: Some(vec![vec![RouteHintHop {
+                id: "02bb".to_owned(),
+                short_channel_id: "1x2x3".to_owned(),
+                fee_base_msat: MSat(1),
+                fee_proportional_millionths: 10,
+                cltv_expiry_delta: 6,
+            }]]),
+            extratlvs: Some(extratlvs),
+            ..KeysendOptions::default()
+        };
+        let payment = check_call(
+            "keysend",
+            json!({
+                "destination": "02aa",
+                "amount_msat": 1000,
+                "maxfee": 10,
+                "routehints": [[{
+                    "id": "02bb",
+                    "short_channel_id": "1x2x3",
+                    "fee_base_msat": 1,
+                    "fee_proportional_millionths": 10,
+                    "cltv_expiry_delta": 6
+                }]],
+                "extratlvs": { "133773310": "68656c6c6f" }
+            }),
+            json!({
+                "payment_hash": "00",
+                "destination": "02aa",
+                "amount_msat": 1000,
+                "amount_sent_msat": 1001,
+                "created_at": 1700000000.5,
+                "status": "complete",
+                "payment_preimage": "11",
+                "parts": 1
+            }),
+            |lightning| lightning.keysend("02aa", 1000, options),
+        );
+        assert_eq!(payment.amount_sent_msat, MSat(1001));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn txprepare_outputs_and_psbt() {
+        use serde_json::json;
+
+        use crate::requests::{AmountOrAll, TxOutput};
+        use crate::types::Psbt;
+
+        let psbt = Psbt::from_bytes(b"psbt\xff\x01\x00").unwrap();
+        let outputs = [
+            TxOutput {
+                address: "bcrt1qaaa",
+                amount: AmountOrAll::Amount(1000),
+            },
+            TxOutput {
+                address: "bcrt1qbbb",
+                amount: AmountOrAll::All,
+            },
+        ];
+        let prepared = check_call(
+            "txprepare",
+            json!({ "outputs": [{ "bcrt1qaaa": 1000 }, { "bcrt1qbbb": "all" }] }),
+            json!({ "psbt": psbt.as_str(), "unsigned_tx": "0200", "txid": "aa" }),
+            |lightning| lightning.txprepare(&outputs, None, None, None),
+        );
+        assert_eq!(prepared.psbt, psbt);
+
+        // the PSBT of the response is checked
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning
+            .client
+            .expect("txsend", json!({ "psbt": "bm90IGEgcHNidA==" }));
+        assert!(lightning.txsend("aa").is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn open_dual_funded_channel_round_trips() {
+        use clightningrpc_common::transport::Mock;
+        use serde_json::json;
+
+        use super::{LightningRPC, OpenChannelOptions};
+        use crate::types::Psbt;
+
+        let initial = Psbt::from_bytes(b"psbt\xff\x00").unwrap();
+        let negotiated = Psbt::from_bytes(b"psbt\xff\x01").unwrap();
+        let signed = Psbt::from_bytes(b"psbt\xff\x02").unwrap();
+        let unreserved = json!({ "reservations": [{
+            "txid": "aa",
+            "vout": 0,
+            "was_reserved": true,
+            "reserved": false
+        }]});
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning.client.expect(
+            "openchannel_init",
+            json!({
+                "channel_id": "cc",
+                "psbt": initial,
+                "commitments_secured": false,
+                "funding_serial": 1
+            }),
+        );
+        for commitments_secured in [false, true] {
+            lightning.client.expect(
+                "openchannel_update",
+                json!({
+                    "channel_id": "cc",
+                    "psbt": negotiated,
+                    "commitments_secured": commitments_secured,
+                    "funding_outnum": 0
+                }),
+            );
+        }
+        lightning
+            .client
+            .expect("signpsbt", json!({ "signed_psbt": signed }));
+        lightning.client.expect(
+            "openchannel_signed",
+            json!({ "channel_id": "cc", "tx": "0200", "txid": "aa" }),
+        );
+        let opened = lightning
+            .open_dual_funded_channel("02aa", 100000, &initial, OpenChannelOptions::default())
+            .unwrap();
+        assert_eq!(opened.txid, "aa");
+        let requests = lightning.client.requests();
+        let methods: Vec<&str> = requests.iter().map(|(method, _)| method.as_str()).collect();
+        assert_eq!(
+            methods,
+            [
+                "openchannel_init",
+                "openchannel_update",
+                "openchannel_update",
+                "signpsbt",
+                "openchannel_signed"
+            ]
+        );
+        assert_eq!(requests[4].1["signed_psbt"], json!(signed));
+
+        // a failure after openchannel_init aborts the opening
+        lightning.client.expect(
+            "openchannel_init",
+            json!({
+                "channel_id": "dd",
+                "psbt": initial,
+                "commitments_secured": true,
+                "funding_serial": 1
+            }),
+        );
+        lightning.client.expect(
+            "openchannel_abort",
+            json!({ "channel_id": "dd", "channel_canceled": true, "reason": "aborted" }),
+        );
+        lightning
+            .client
+            .expect("unreserveinputs", unreserved.clone());
+        assert!(lightning
+            .open_dual_funded_channel("02aa", 100000, &initial, OpenChannelOptions::default())
+            .is_err());
+        let requests = lightning.client.requests();
+        let (method, params) = &requests[requests.len() - 2];
+        assert_eq!(method, "openchannel_abort");
+        assert_eq!(params["channel_id"], "dd");
+        let (method, params) = &requests[requests.len() - 1];
+        assert_eq!(method, "unreserveinputs");
+        assert_eq!(params, &json!({ "psbt": initial }));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn splice_channel_gives_up_after_the_update_rounds() {
+        use clightningrpc_common::transport::Mock;
+        use serde_json::json;
+
+        use super::{LightningRPC, MAX_UPDATE_ROUNDS};
+        use crate::types::Psbt;
+
+        let initial = Psbt::from_bytes(b"psbt\xff\x00").unwrap();
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning
+            .client
+            .expect("splice_init", json!({ "psbt": initial }));
+        for _ in 0..MAX_UPDATE_ROUNDS {
+            lightning.client.expect(
+                "splice_update",
+                json!({ "psbt": initial, "commitments_secured": false }),
+            );
+        }
+        lightning.client.expect(
+            "unreserveinputs",
+            json!({ "reservations": [{
+                "txid": "aa",
+                "vout": 0,
+                "was_reserved": true,
+                "reserved": false
+            }]}),
+        );
+        match lightning.splice_channel("cc", 100000, Some(&initial), None) {
+            Err(Error::CommitmentsNotSecured) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        let requests = lightning.client.requests();
+        assert_eq!(requests.len(), MAX_UPDATE_ROUNDS + 2);
+        assert_eq!(requests[MAX_UPDATE_ROUNDS].0, "splice_update");
+        assert_eq!(
+            requests[MAX_UPDATE_ROUNDS + 1],
+            ("unreserveinputs".to_owned(), json!({ "psbt": initial }))
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn createrune_restrictions() {
+        use serde_json::json;
+
+        use crate::rune::{Alternative, Condition, Restriction};
+
+        let restrictions = [
+            Restriction::new(Alternative::method("listfunds")).or(Alternative::method("pay")),
+            Alternative::pname("amount_msat", Condition::IntLess, "1000").into(),
+        ];
+        let rune = check_call(
+            "createrune",
+            json!({ "restrictions": [
+                ["method=listfunds", "method=pay"],
+                ["pnameamount_msat<1000"]
+            ]}),
+            json!({ "rune": "abc", "unique_id": "1" }),
+            |lightning| lightning.createrune(None, Some(&restrictions)),
+        );
+        assert_eq!(rune.unique_id, "1");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn routing_node_analytics() {
+        use serde_json::json;
+
+        use super::ListForwardsOptions;
+        use crate::requests::{ListIndex, Pagination};
+        use crate::responses;
+        use crate::types::{ForwardStatus, MSat};
+
+        // shape of 'feerates' since v23.05, without the deprecated fields
+        let feerates: responses::FeeRates = serde_json::from_value(json!({
+            "perkw": {
+                "opening": 1012,
+                "mutual_close": 506,
+                "unilateral_close": 1012,
+                "unilateral_anchor_close": 506,
+                "penalty": 506,
+                "min_acceptable": 253,
+                "max_acceptable": 40480,
+                "floor": 253,
+                "estimates": [{ "blockcount": 2, "feerate": 1012, "smoothed_feerate": 1012 }]
+            },
+            "onchain_fee_estimates": {
+                "opening_channel_satoshis": 712,
+                "mutual_close_satoshis": 340,
+                "unilateral_close_satoshis": 761,
+                "unilateral_close_nonanchor_satoshis": 605,
+                "htlc_timeout_satoshis": 668,
+                "htlc_success_satoshis": 711
+            }
+        }))
+        .unwrap();
+        let perkw = feerates.perkw.unwrap();
+        assert_eq!(perkw.estimates.unwrap()[0].blockcount, 2);
+        assert!(perkw.delayed_to_us.is_none());
+
+        let options = ListForwardsOptions {
+            status: Some(ForwardStatus::LocalFailed),
+            pagination: Some(Pagination {
+                index: ListIndex::Created,
+                start: Some(7),
+                limit: None,
+            }),
+            ..ListForwardsOptions::default()
+        };
+        let forwards = check_call(
+            "listforwards",
+            json!({ "status": "local_failed", "index": "created", "start": 7 }),
+            json!({ "forwards": [{
+                "created_index": 7,
+                "in_channel": "1x2x3",
+                "in_msat": 1001,
+                "status": "local_failed",
+                "received_time": 1700000000.25
+            }]}),
+            |lightning| lightning.listforwards(options),
+        )
+        .forwards;
+        assert_eq!(forwards[0].status, ForwardStatus::LocalFailed);
+        assert_eq!(forwards[0].in_msat, MSat(1001));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn paginated_lists() {
+        use serde_json::json;
+
+        use crate::requests::{ListIndex, Pagination};
+
+        let pagination = Pagination {
+            index: ListIndex::Updated,
+            start: Some(12),
+            limit: Some(50),
+        };
+        check_call(
+            "listinvoices",
+            json!({ "offer_id": "ab", "index": "updated", "start": 12, "limit": 50 }),
+            json!({ "invoices": [] }),
+            |lightning| lightning.listinvoices_paginated(None, None, None, Some("ab"), pagination),
+        );
+        check_call(
+            "listsendpays",
+            json!({ "index": "updated", "start": 12, "limit": 50 }),
+            json!({ "payments": [] }),
+            |lightning| lightning.listsendpays_paginated(None, None, pagination),
+        );
+        check_call(
+            "listsendpays",
+            json!({ "payment_hash": "cd" }),
+            json!({ "payments": [] }),
+            |lightning| {
+                #[allow(deprecated)]
+                lightning.listsendpays(None, Some("cd"))
+            },
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn signmessage_and_backups() {
+        use serde_json::json;
+
+        let signed = check_call(
+            "signmessage",
+            json!({ "message": "hello" }),
+            json!({ "signature": "aa", "recid": "01", "zbase": "d75ge4" }),
+            |lightning| lightning.signmessage("hello"),
+        );
+        assert_eq!(signed.zbase.as_str(), "d75ge4");
+        assert_eq!(signed.recid.to_bytes().unwrap(), [1]);
+        // '0' and 'l' are not in the zbase32 alphabet
+        let lightning = LightningRPC::with_transport(Mock::new());
+        lightning.client.expect(
+            "signmessage",
+            json!({ "signature": "aa", "recid": "01", "zbase": "0l" }),
+        );
+        assert!(lightning.signmessage("hello").is_err());
+
+        let scb = check_call(
+            "staticbackup",
+            json!({}),
+            json!({ "scb": ["0011", "2233"] }),
+            |lightning| lightning.staticbackup(),
+        )
+        .scb;
+        assert_eq!(scb[1].to_bytes().unwrap(), [0x22, 0x33]);
+        let recovered = check_call(
+            "recoverchannel",
+            json!({ "scb": ["0011", "2233"] }),
+            json!({ "stubs": ["cc", "dd"] }),
+            |lightning| lightning.recoverchannel(&scb),
+        );
+        assert_eq!(recovered.stubs.len(), 2);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn configs_and_plugins() {
+        use serde_json::json;
+
+        use crate::types::{MSat, PluginSubcommand};
+
+        let configs = check_call(
+            "listconfigs",
+            json!({}),
+            json!({ "configs": {
+                "alias": { "value_str": "node", "source": "/etc/lightning/config:3" },
+                "fee-base": { "value_int": 1000, "source": "default", "dynamic": true },
+                "min-capacity-sat": { "value_msat": 10000, "source": "default" },
+                "addr": { "values_str": ["0.0.0.0:9735"], "sources": ["cmdline"] },
+                "developer": { "set": false, "source": "default" },
+                "clnrest-port": { "value_int": 3010, "source": "cmdline", "plugin": "/p/clnrest" }
+            }}),
+            |lightning| lightning.listconfigs_v2(None),
+        )
+        .configs;
+        assert_eq!(configs["fee-base"].value_int, Some(1000));
+        assert_eq!(configs["fee-base"].dynamic, Some(true));
+        assert_eq!(configs["min-capacity-sat"].value_msat, Some(MSat(10000)));
+        assert_eq!(configs["addr"].sources.as_ref().unwrap()[0], "cmdline");
+        assert_eq!(
+            configs["clnrest-port"].plugin.as_deref(),
+            Some("/p/clnrest")
+        );
+
+        let started = check_call(
+            "plugin",
+            json!({ "subcommand": "start", "plugin": "/p/lsp", "lsp-fee-ppm": "1000" }),
+            json!({
+                "command": "start",
+                "plugins": [{ "name": "/p/lsp", "active": true, "dynamic": true }]
+            }),
+            |lightning| lightning.plugin_start("/p/lsp", &[("lsp-fee-ppm", "1000")]),
+        );
+        assert_eq!(started.command, PluginSubcommand::Start);
+        assert!(started.plugins.unwrap()[0].active);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn sendcustommsg_wire_format() {
+        use serde_json::json;
+
+        use crate::types::CustomMessage;
+
+        let message = CustomMessage::new(48001, &[0xca, 0xfe]).unwrap();
+        check_call(
+            "sendcustommsg",
+            json!({ "node_id": "02aa", "msg": "bb81cafe" }),
+            json!({ "status": "Message sent to connectd for delivery" }),
+            |lightning| lightning.sendcustommsg("02aa", &message),
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn getroutes_for_sendpay() {
+        use serde_json::json;
+
+        use super::GetRoutesOptions;
+        use crate::types::{AskreneInform, MSat};
+
+        let informed = check_call(
+            "askrene-inform-channel",
+            json!({
+                "layer": "mine",
+                "short_channel_id_dir": "103x1x0/1",
+                "amount_msat": 500000,
+                "inform": "constrained"
+            }),
+            json!({ "constraints": [{
+                "short_channel_id_dir": "103x1x0/1",
+                "layer": "mine",
+                "timestamp": 1700000000,
+                "maximum_msat": 499999
+            }]}),
+            |lightning| {
+                lightning.askrene_inform_channel(
+                    "mine",
+                    "103x1x0/1",
+                    Some(500000),
+                    Some(AskreneInform::Constrained),
+                )
+            },
+        );
+        assert_eq!(informed.constraints[0].maximum_msat, Some(MSat(499999)));
+        let routes = check_call(
+            "getroutes",
+            json!({
+                "source": "02aa",
+                "destination": "02cc",
+                "amount_msat": 100000,
+                "layers": ["auto.localchans", "mine"],
+                "maxfee_msat": 1000,
+                "final_cltv": 18,
+                "maxparts": 4
+            }),
+            json!({
+                "probability_ppm": 900000,
+                "routes": [{
+                    "probability_ppm": 900000,
+                    "amount_msat": 100000,
+                    "final_cltv": 18,
+                    "path": [
+                        {
+                            "short_channel_id_dir": "103x1x0/1",
+                            "next_node_id": "02bb",
+                            "amount_msat": 100001,
+                            "delay": 24
+                        },
+                        {
+                            "short_channel_id_dir": "105x2x1/0",
+                            "next_node_id": "02cc",
+                            "amount_msat": 100000,
+                            "delay": 18
+                        }
+                    ]
+                }]
+            }),
+            |lightning| {
+                lightning.getroutes(
+                    "02aa",
+                    "02cc",
+                    100000,
+                    &["auto.localchans", "mine"],
+                    1000,
+                    18,
+                    GetRoutesOptions {
+                        maxparts: Some(4),
+                        ..Default::default()
+                    },
+                )
+            },
+        );
+        let route = routes.routes[0].sendpay_route().unwrap();
+        assert_eq!(route[0].channel, "103x1x0");
+        assert_eq!(route[0].direction, Some(1));
+        assert_eq!(route[0].id, "02bb");
+        assert_eq!(route[1].amount_msat, MSat(100000));
+        assert_eq!(route[1].delay, 18);
+
+        let mut route = routes.routes[0].clone();
+        route.path[1].short_channel_id_dir = "105x2x1".to_owned();
+        assert_eq!(route.sendpay_route().unwrap()[1].direction, None);
+        route.path[1].short_channel_id_dir = "105x2x1/2".to_owned();
+        assert!(route.sendpay_route().is_err());
     }
 }
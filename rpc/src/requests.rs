@@ -14,6 +14,8 @@
 //
 //! Structures representing requests to API calls
 #![allow(missing_docs)]
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::types;
@@ -76,6 +78,20 @@ pub struct ListPeers<'a> {
     pub level: Option<&'a str>,
 }
 
+/// 'listpeerchannels' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListPeerChannels<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+}
+
+/// 'listclosedchannels' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListClosedChannels<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+}
+
 /// 'listinvoices' command
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ListInvoices<'a> {
@@ -87,6 +103,8 @@ pub struct ListInvoices<'a> {
     pub payment_hash: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offer_id: Option<&'a str>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
 }
 
 /// 'createinvoice' command
@@ -181,6 +199,47 @@ pub struct Pay<'a> {
     pub maxdelay: Option<u64>,
 }
 
+/// 'keysend' command
+#[derive(Debug, Clone, Serialize)]
+pub struct Keysend<'a> {
+    pub destination: &'a str,
+    pub amount_msat: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxfeepercent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_for: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxdelay: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exemptfee: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxfee: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routehints: Option<Vec<Vec<types::RouteHintHop>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extratlvs: Option<BTreeMap<u64, String>>,
+}
+
+/// 'xpay' command
+#[derive(Debug, Clone, Serialize)]
+pub struct XPay<'a> {
+    pub invstring: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxfee: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layers: Option<Vec<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_for: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxdelay: Option<u64>,
+}
+
 /// 'sendpay' command
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SendPay<'a> {
@@ -206,6 +265,8 @@ pub struct ListSendPays<'a> {
     pub bolt11: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_hash: Option<&'a str>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
 }
 
 /// 'decodepay' command
@@ -319,3 +380,782 @@ pub struct NewAddr<'a> {
 /// 'stop' command
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Stop {}
+
+/// enum type that can either hold an amount in millisatoshi, or Any
+#[derive(Debug, Clone)]
+pub enum AmountOrAny {
+    Any,
+    Amount(u64),
+}
+
+impl Serialize for AmountOrAny {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            AmountOrAny::Amount(a) => serializer.serialize_str(&format!("{a}msat")),
+            AmountOrAny::Any => serializer.serialize_str("any"),
+        }
+    }
+}
+
+/// 'offer' command
+#[derive(Debug, Clone, Serialize)]
+pub struct Offer<'a> {
+    pub amount: AmountOrAny,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity_max: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absolute_expiry: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_base: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_paywindow: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_use: Option<bool>,
+}
+
+/// 'listoffers' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListOffers<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offer_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_only: Option<bool>,
+}
+
+/// 'disableoffer' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DisableOffer<'a> {
+    pub offer_id: &'a str,
+}
+
+/// 'enableoffer' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnableOffer<'a> {
+    pub offer_id: &'a str,
+}
+
+/// 'fetchinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FetchInvoice<'a> {
+    pub offer: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_counter: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payer_note: Option<&'a str>,
+}
+
+/// 'sendinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendInvoice<'a> {
+    pub invreq: &'a str,
+    pub label: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u64>,
+}
+
+/// 'invoicerequest' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InvoiceRequest<'a> {
+    pub amount: u64,
+    pub description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absolute_expiry: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_use: Option<bool>,
+}
+
+/// 'listinvoicerequests' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListInvoiceRequests<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invreq_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_only: Option<bool>,
+}
+
+/// 'disableinvoicerequest' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DisableInvoiceRequest<'a> {
+    pub invreq_id: &'a str,
+}
+
+/// 'decode' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Decode<'a> {
+    pub string: &'a str,
+}
+
+/// Output of 'txprepare' and 'multiwithdraw', sending {amount} to {address}
+#[derive(Debug, Clone)]
+pub struct TxOutput<'a> {
+    pub address: &'a str,
+    pub amount: AmountOrAll,
+}
+
+impl Serialize for TxOutput<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.address, &self.amount)?;
+        map.end()
+    }
+}
+
+/// 'fundpsbt' command
+#[derive(Debug, Clone, Serialize)]
+pub struct FundPsbt<'a> {
+    pub satoshi: AmountOrAll,
+    pub feerate: &'a str,
+    pub startweight: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minconf: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locktime: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_witness_weight: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excess_as_change: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonwrapped: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opening_anchor_channel: Option<bool>,
+}
+
+/// 'utxopsbt' command
+#[derive(Debug, Clone, Serialize)]
+pub struct UtxoPsbt<'a> {
+    pub satoshi: AmountOrAll,
+    pub feerate: &'a str,
+    pub startweight: u32,
+    pub utxos: &'a [&'a str],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservedok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locktime: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_witness_weight: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excess_as_change: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opening_anchor_channel: Option<bool>,
+}
+
+/// 'reserveinputs' command
+#[derive(Debug, Clone, Serialize)]
+pub struct ReserveInputs<'a> {
+    pub psbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u32>,
+}
+
+/// 'unreserveinputs' command
+#[derive(Debug, Clone, Serialize)]
+pub struct UnreserveInputs<'a> {
+    pub psbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u32>,
+}
+
+/// 'signpsbt' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SignPsbt<'a> {
+    pub psbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signonly: Option<&'a [u32]>,
+}
+
+/// 'sendpsbt' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SendPsbt<'a> {
+    pub psbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u32>,
+}
+
+/// 'txprepare' command
+#[derive(Debug, Clone, Serialize)]
+pub struct TxPrepare<'a> {
+    pub outputs: &'a [TxOutput<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minconf: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utxos: Option<&'a [&'a str]>,
+}
+
+/// 'txsend' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxSend<'a> {
+    pub txid: &'a str,
+}
+
+/// 'txdiscard' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxDiscard<'a> {
+    pub txid: &'a str,
+}
+
+/// 'listtransactions' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListTransactions {}
+
+/// 'listaddresses' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListAddresses<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// 'multiwithdraw' command
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiWithdraw<'a> {
+    pub outputs: &'a [TxOutput<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minconf: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utxos: Option<&'a [&'a str]>,
+}
+
+/// Destination of 'multifundchannel', opening a channel of {amount} with {id}
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiFundDestination<'a> {
+    pub id: &'a str,
+    pub amount: AmountOrAll,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_to: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_amt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compact_lease: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mindepth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u64>,
+}
+
+/// 'multifundchannel' command
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiFundChannel<'a> {
+    pub destinations: &'a [MultiFundDestination<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minconf: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utxos: Option<&'a [&'a str]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minchannels: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment_feerate: Option<&'a str>,
+}
+
+/// 'fundchannel_start' command
+#[derive(Debug, Clone, Serialize)]
+pub struct FundChannelStart<'a> {
+    pub id: &'a str,
+    pub amount: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_to: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mindepth: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserve: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_type: Option<&'a [u32]>,
+}
+
+/// 'fundchannel_complete' command
+#[derive(Debug, Clone, Serialize)]
+pub struct FundChannelComplete<'a> {
+    pub id: &'a str,
+    pub psbt: &'a types::Psbt,
+}
+
+/// 'fundchannel_cancel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundChannelCancel<'a> {
+    pub id: &'a str,
+}
+
+/// 'openchannel_init' command
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenChannelInit<'a> {
+    pub id: &'a str,
+    pub amount: u64,
+    pub initialpsbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment_feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_feerate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_to: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_amt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compact_lease: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_type: Option<&'a [u32]>,
+}
+
+/// 'openchannel_update' command
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenChannelUpdate<'a> {
+    pub channel_id: &'a str,
+    pub psbt: &'a types::Psbt,
+}
+
+/// 'openchannel_signed' command
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenChannelSigned<'a> {
+    pub channel_id: &'a str,
+    pub signed_psbt: &'a types::Psbt,
+}
+
+/// 'openchannel_bump' command
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenChannelBump<'a> {
+    pub channel_id: &'a str,
+    pub amount: u64,
+    pub initialpsbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_feerate: Option<&'a str>,
+}
+
+/// 'openchannel_abort' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenChannelAbort<'a> {
+    pub channel_id: &'a str,
+}
+
+/// 'splice_init' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SpliceInit<'a> {
+    pub channel_id: &'a str,
+    pub relative_amount: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initialpsbt: Option<&'a types::Psbt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feerate_per_kw: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_feerate: Option<bool>,
+}
+
+/// 'splice_update' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SpliceUpdate<'a> {
+    pub channel_id: &'a str,
+    pub psbt: &'a types::Psbt,
+}
+
+/// 'splice_signed' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SpliceSigned<'a> {
+    pub channel_id: &'a str,
+    pub psbt: &'a types::Psbt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_first: Option<bool>,
+}
+
+/// How 'datastore' treats an existing entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DatastoreMode {
+    /// fail if the key already exists (the default)
+    MustCreate,
+    /// fail if the key does not exist yet
+    MustReplace,
+    /// create the key, or replace its data
+    CreateOrReplace,
+    /// fail if the key does not exist yet, append to its data otherwise
+    MustAppend,
+    /// create the key, or append to its data
+    CreateOrAppend,
+}
+
+/// Data of a 'datastore' entry
+#[derive(Debug, Clone, Copy)]
+pub enum DatastoreData<'a> {
+    /// UTF-8 string
+    String(&'a str),
+    /// hex-encoded bytes
+    Hex(&'a str),
+}
+
+/// 'datastore' command
+#[derive(Debug, Clone, Serialize)]
+pub struct Datastore<'a> {
+    pub key: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<DatastoreMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation: Option<u64>,
+}
+
+/// 'deldatastore' command
+#[derive(Debug, Clone, Serialize)]
+pub struct DelDatastore<'a> {
+    pub key: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation: Option<u64>,
+}
+
+/// 'listdatastore' command
+#[derive(Debug, Clone, Serialize)]
+pub struct ListDatastore<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Vec<&'a str>>,
+}
+
+/// 'datastoreusage' command
+#[derive(Debug, Clone, Serialize)]
+pub struct DatastoreUsage<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Vec<&'a str>>,
+}
+
+/// 'createrune' command
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateRune<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rune: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Vec<Vec<String>>>,
+}
+
+/// 'showrunes' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShowRunes<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rune: Option<&'a str>,
+}
+
+/// 'blacklistrune' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BlacklistRune {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relist: Option<bool>,
+}
+
+/// 'checkrune' command
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckRune<'a> {
+    pub rune: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodeid: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<&'a serde_json::Value>,
+}
+
+/// Index that the 'list' commands paginate on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListIndex {
+    /// the order in which the entries were created
+    Created,
+    /// the order in which the entries were last changed
+    Updated,
+}
+
+/// Pagination of the 'list' commands, {limit} entries from {start} in the {index} order
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Pagination {
+    pub index: ListIndex,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// 'listforwards' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListForwards<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<types::ForwardStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_channel: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_channel: Option<&'a str>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+/// 'listhtlcs' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListHtlcs<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+/// 'setchannel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetChannel<'a> {
+    pub id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feebase: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feeppm: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htlcmin: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htlcmax: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcedelay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignorefeelimits: Option<bool>,
+}
+
+/// 'wait' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Wait {
+    pub subsystem: types::WaitSubsystem,
+    pub indexname: types::WaitIndex,
+    pub nextvalue: u64,
+}
+
+/// 'signmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignMessage<'a> {
+    pub message: &'a str,
+}
+
+/// 'checkmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CheckMessage<'a> {
+    pub message: &'a str,
+    pub zbase: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<&'a str>,
+}
+
+/// 'signinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignInvoice<'a> {
+    pub invstring: &'a str,
+}
+
+/// 'makesecret' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MakeSecret<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string: Option<&'a str>,
+}
+
+/// 'getemergencyrecoverdata' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetEmergencyRecoverData {}
+
+/// 'emergencyrecover' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmergencyRecover {}
+
+/// 'recoverchannel' command
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoverChannel<'a> {
+    pub scb: &'a [types::Hex],
+}
+
+/// 'staticbackup' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StaticBackup {}
+
+/// 'setconfig' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetConfig<'a> {
+    pub config: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub val: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transient: Option<bool>,
+}
+
+/// 'plugin' command
+#[derive(Debug, Clone, Serialize)]
+pub struct Plugin<'a> {
+    pub subcommand: types::PluginSubcommand,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<&'a str>,
+    #[serde(flatten)]
+    pub options: BTreeMap<&'a str, &'a str>,
+}
+
+/// 'sendcustommsg' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SendCustomMsg<'a> {
+    pub node_id: &'a str,
+    pub msg: types::Hex,
+}
+
+/// Hop of 'sendonionmessage', with the encrypted {tlv} payload for node {id}
+#[derive(Debug, Clone, Serialize)]
+pub struct OnionMessageHop<'a> {
+    pub id: &'a str,
+    pub tlv: &'a types::Hex,
+}
+
+/// 'sendonionmessage' command
+#[derive(Debug, Clone, Serialize)]
+pub struct SendOnionMessage<'a> {
+    pub first_id: &'a str,
+    pub path_key: &'a str,
+    pub hops: &'a [OnionMessageHop<'a>],
+}
+
+/// 'injectonionmessage' command
+#[derive(Debug, Clone, Serialize)]
+pub struct InjectOnionMessage<'a> {
+    pub path_key: &'a str,
+    pub message: &'a types::Hex,
+}
+
+/// 'getroutes' command
+#[derive(Debug, Clone, Serialize)]
+pub struct GetRoutes<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    pub amount_msat: u64,
+    pub layers: &'a [&'a str],
+    pub maxfee_msat: u64,
+    pub final_cltv: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxdelay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxparts: Option<u32>,
+}
+
+/// 'askrene-create-layer' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneCreateLayer<'a> {
+    pub layer: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent: Option<bool>,
+}
+
+/// 'askrene-inform-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneInformChannel<'a> {
+    pub layer: &'a str,
+    pub short_channel_id_dir: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inform: Option<types::AskreneInform>,
+}
+
+/// 'askrene-update-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneUpdateChannel<'a> {
+    pub layer: &'a str,
+    pub short_channel_id_dir: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htlc_minimum_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub htlc_maximum_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_base_msat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_proportional_millionths: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cltv_expiry_delta: Option<u16>,
+}
+
+/// 'askrene-create-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneCreateChannel<'a> {
+    pub layer: &'a str,
+    pub source: &'a str,
+    pub destination: &'a str,
+    pub short_channel_id: &'a str,
+    pub capacity_msat: u64,
+}
+
+/// 'askrene-listlayers' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneListLayers<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<&'a str>,
+}
+
+/// 'askrene-age' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneAge<'a> {
+    pub layer: &'a str,
+    pub cutoff: u64,
+}
@@ -14,11 +14,14 @@
 //
 #![allow(missing_docs)]
 //! Structures representing responses to API calls
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::{Ipv4Addr, Ipv6Addr};
 
-use crate::types::{MSat, RouteItem};
+use crate::errors::Error;
+use crate::types::{
+    ForwardStatus, Hex, MSat, PluginSubcommand, Psbt, RouteItem, WaitIndex, WaitSubsystem, ZBase32,
+};
 
 /// structure for network addresses
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -52,6 +55,14 @@ pub struct GetInfo {
     pub warning_lightningd_sync: Option<String>,
 }
 
+/// Sub-structure for 'estimates' in 'feerates'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeeRatesEstimate {
+    pub blockcount: u32,
+    pub feerate: u64,
+    pub smoothed_feerate: u64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FeeRatesInner {
     pub urgent: Option<u64>,
@@ -60,11 +71,14 @@ pub struct FeeRatesInner {
     pub opening: u64,
     pub mutual_close: u64,
     pub unilateral_close: u64,
-    pub delayed_to_us: u64,
-    pub htlc_resolution: u64,
+    pub unilateral_anchor_close: Option<u64>,
+    pub delayed_to_us: Option<u64>,
+    pub htlc_resolution: Option<u64>,
     pub penalty: u64,
     pub min_acceptable: u64,
     pub max_acceptable: u64,
+    pub floor: Option<u64>,
+    pub estimates: Option<Vec<FeeRatesEstimate>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -72,6 +86,7 @@ pub struct FeeRatesOnchain {
     pub opening_channel_satoshis: u64,
     pub mutual_close_satoshis: u64,
     pub unilateral_close_satoshis: u64,
+    pub unilateral_close_nonanchor_satoshis: Option<u64>,
     pub htlc_timeout_satoshis: u64,
     pub htlc_success_satoshis: u64,
 }
@@ -108,6 +123,7 @@ pub struct ListChannelsItem {
     pub source: String,
     pub destination: String,
     pub short_channel_id: String,
+    pub direction: Option<u32>,
     pub public: bool,
     pub amount_msat: MSat,
     pub message_flags: u64,
@@ -165,9 +181,35 @@ pub struct GetLog {
     pub log: Vec<LogEntry>,
 }
 
+/// Sub-structure for 'configs' items in 'listconfigs' and 'config' in 'setconfig'
+///
+/// Only the `value_*` field matching the type of the option is set, or
+/// the `values_*` one (and `sources`) for the options given multiple times.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Config {
+    pub source: Option<String>,
+    pub sources: Option<Vec<String>>,
+    pub plugin: Option<String>,
+    pub dynamic: Option<bool>,
+    pub set: Option<bool>,
+    pub value_str: Option<String>,
+    pub value_msat: Option<MSat>,
+    pub value_int: Option<i64>,
+    pub value_bool: Option<bool>,
+    pub values_str: Option<Vec<String>>,
+    pub values_int: Option<Vec<i64>>,
+    pub values_bool: Option<Vec<bool>>,
+}
+
 /// 'listconfigs' command
 pub type ListConfigs = HashMap<String, serde_json::Value>;
 
+/// 'listconfigs' command, in the `configs` layout of lightningd v23.08 and later
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListConfigsV2 {
+    pub configs: HashMap<String, Config>,
+}
+
 /// Sub-structure for htlcs in 'listpeers'
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Htlc {
@@ -225,8 +267,11 @@ pub struct Channel {
 pub struct Peer {
     pub id: String,
     pub connected: bool,
+    pub num_channels: Option<u64>,
     pub netaddr: Option<Vec<String>>,
     pub features: Option<String>,
+    /// only filled by the nodes older than v23.02, see 'listpeerchannels'
+    #[serde(default)]
     pub channels: Vec<Channel>,
     pub log: Option<Vec<LogEntry>>,
 }
@@ -237,9 +282,200 @@ pub struct ListPeers {
     pub peers: Vec<Peer>,
 }
 
+/// Sub-structure for 'channel_type' in 'listpeerchannels' and 'listclosedchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelType {
+    pub bits: Vec<u32>,
+    pub names: Vec<String>,
+}
+
+/// Sub-structure for 'alias' in 'listpeerchannels' and 'listclosedchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelAlias {
+    pub local: Option<String>,
+    pub remote: Option<String>,
+}
+
+/// Sub-structure for 'feerate' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelFeerate {
+    pub perkw: u64,
+    pub perkb: u64,
+}
+
+/// Sub-structure for 'inflight' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelInflight {
+    pub funding_txid: String,
+    pub funding_outnum: u32,
+    pub feerate: String,
+    pub total_funding_msat: MSat,
+    pub our_funding_msat: MSat,
+    pub splice_amount: Option<i64>,
+    pub scratch_txid: Option<String>,
+}
+
+/// Sub-structure for 'funding' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelFunding {
+    pub local_funds_msat: MSat,
+    pub remote_funds_msat: MSat,
+    pub pushed_msat: Option<MSat>,
+    pub fee_paid_msat: Option<MSat>,
+    pub fee_rcvd_msat: Option<MSat>,
+}
+
+/// Sub-structure for the channel update of each side in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelUpdate {
+    pub htlc_minimum_msat: MSat,
+    pub htlc_maximum_msat: MSat,
+    pub cltv_expiry_delta: u32,
+    pub fee_base_msat: MSat,
+    pub fee_proportional_millionths: u32,
+}
+
+/// Sub-structure for 'updates' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelUpdates {
+    pub local: ChannelUpdate,
+    pub remote: Option<ChannelUpdate>,
+}
+
+/// Sub-structure for 'state_changes' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelStateChange {
+    pub timestamp: String,
+    pub old_state: String,
+    pub new_state: String,
+    pub cause: String,
+    pub message: String,
+}
+
+/// Sub-structure for 'htlcs' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PeerChannelHtlc {
+    pub direction: String,
+    pub id: u64,
+    pub amount_msat: MSat,
+    pub expiry: u64,
+    pub payment_hash: String,
+    pub local_trimmed: Option<bool>,
+    pub status: Option<String>,
+    pub state: String,
+}
+
+/// Sub-structure for 'channels' in 'listpeerchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PeerChannel {
+    pub peer_id: String,
+    pub peer_connected: bool,
+    pub reestablished: Option<bool>,
+    pub state: String,
+    pub scratch_txid: Option<String>,
+    pub channel_type: Option<ChannelType>,
+    pub feerate: Option<ChannelFeerate>,
+    pub owner: Option<String>,
+    pub short_channel_id: Option<String>,
+    pub direction: Option<u32>,
+    pub channel_id: Option<String>,
+    pub funding_txid: Option<String>,
+    pub funding_outnum: Option<u32>,
+    pub initial_feerate: Option<String>,
+    pub last_feerate: Option<String>,
+    pub next_feerate: Option<String>,
+    pub next_fee_step: Option<u32>,
+    pub inflight: Option<Vec<ChannelInflight>>,
+    pub close_to: Option<String>,
+    pub close_to_addr: Option<String>,
+    pub ignore_fee_limits: Option<bool>,
+    pub private: Option<bool>,
+    pub opener: String,
+    pub closer: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub funding: Option<ChannelFunding>,
+    pub to_us_msat: Option<MSat>,
+    pub min_to_us_msat: Option<MSat>,
+    pub max_to_us_msat: Option<MSat>,
+    pub total_msat: Option<MSat>,
+    pub fee_base_msat: Option<MSat>,
+    pub fee_proportional_millionths: Option<u32>,
+    pub dust_limit_msat: Option<MSat>,
+    pub max_total_htlc_in_msat: Option<MSat>,
+    pub their_reserve_msat: Option<MSat>,
+    pub our_reserve_msat: Option<MSat>,
+    pub spendable_msat: Option<MSat>,
+    pub receivable_msat: Option<MSat>,
+    pub minimum_htlc_in_msat: Option<MSat>,
+    pub minimum_htlc_out_msat: Option<MSat>,
+    pub maximum_htlc_out_msat: Option<MSat>,
+    pub their_to_self_delay: Option<u32>,
+    pub our_to_self_delay: Option<u32>,
+    pub max_accepted_htlcs: Option<u32>,
+    pub alias: Option<ChannelAlias>,
+    pub updates: Option<ChannelUpdates>,
+    pub state_changes: Option<Vec<ChannelStateChange>>,
+    pub status: Option<Vec<String>>,
+    pub in_payments_offered: Option<u64>,
+    pub in_offered_msat: Option<MSat>,
+    pub in_payments_fulfilled: Option<u64>,
+    pub in_fulfilled_msat: Option<MSat>,
+    pub out_payments_offered: Option<u64>,
+    pub out_offered_msat: Option<MSat>,
+    pub out_payments_fulfilled: Option<u64>,
+    pub out_fulfilled_msat: Option<MSat>,
+    pub last_stable_connection: Option<u64>,
+    pub lost_state: Option<bool>,
+    pub last_tx_fee_msat: Option<MSat>,
+    pub htlcs: Option<Vec<PeerChannelHtlc>>,
+}
+
+/// 'listpeerchannels' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListPeerChannels {
+    pub channels: Vec<PeerChannel>,
+}
+
+/// Sub-structure for 'closedchannels' in 'listclosedchannels'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClosedChannel {
+    pub peer_id: Option<String>,
+    pub channel_id: String,
+    pub short_channel_id: Option<String>,
+    pub alias: Option<ChannelAlias>,
+    pub opener: String,
+    pub closer: Option<String>,
+    pub private: bool,
+    pub channel_type: Option<ChannelType>,
+    pub total_local_commitments: u64,
+    pub total_remote_commitments: u64,
+    pub total_htlcs_sent: u64,
+    pub funding_txid: String,
+    pub funding_outnum: u32,
+    pub leased: bool,
+    pub funding_fee_paid_msat: Option<MSat>,
+    pub funding_fee_rcvd_msat: Option<MSat>,
+    pub funding_pushed_msat: Option<MSat>,
+    pub total_msat: MSat,
+    pub final_to_us_msat: MSat,
+    pub close_cause: String,
+    pub last_commitment_txid: Option<String>,
+    pub last_commitment_fee_msat: Option<MSat>,
+    pub last_stable_connection: Option<u64>,
+}
+
+/// 'listclosedchannels' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListClosedChannels {
+    pub closedchannels: Vec<ClosedChannel>,
+}
+
 /// Sub-structure for invoices in 'listinvoices'
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ListInvoice {
+    pub created_index: Option<u64>,
+    pub updated_index: Option<u64>,
     pub label: String,
     pub bolt11: String,
     pub payment_hash: String,
@@ -339,6 +575,30 @@ pub struct Pay {
     pub parts: u64,
 }
 
+/// 'keysend' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Keysend {
+    pub payment_hash: String,
+    pub destination: Option<String>,
+    pub amount_msat: MSat,
+    pub amount_sent_msat: MSat,
+    pub created_at: f64,
+    pub status: String,
+    pub payment_preimage: String,
+    pub parts: u64,
+    pub warning_partial_completion: Option<String>,
+}
+
+/// 'xpay' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct XPay {
+    pub payment_preimage: String,
+    pub failed_parts: u64,
+    pub successful_parts: u64,
+    pub amount_msat: MSat,
+    pub amount_sent_msat: MSat,
+}
+
 /// 'sendpay' command
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SendPay {
@@ -370,6 +630,8 @@ pub struct SendPay {
 /// Sub-structure for payments in 'listsendpays' and 'waitsendpay'
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ListSendPaysItem {
+    pub created_index: Option<u64>,
+    pub updated_index: Option<u64>,
     pub id: u64,
     pub payment_hash: String,
     pub partid: Option<u64>,
@@ -535,3 +797,998 @@ pub struct NewAddr {
 pub struct Stop {
     pub result: Option<String>,
 }
+
+/// 'offer' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Offer {
+    pub offer_id: String,
+    pub active: bool,
+    pub single_use: bool,
+    pub bolt12: String,
+    pub used: bool,
+    /// false if the same offer already existed
+    pub created: Option<bool>,
+    pub label: Option<String>,
+}
+
+/// 'listoffers' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListOffers {
+    pub offers: Vec<Offer>,
+}
+
+/// 'disableoffer' command
+pub type DisableOffer = Offer;
+
+/// 'enableoffer' command
+pub type EnableOffer = Offer;
+
+/// Sub-structure for 'changes' in 'fetchinvoice'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FetchInvoiceChanges {
+    pub description_appended: Option<String>,
+    pub description: Option<String>,
+    pub vendor_removed: Option<String>,
+    pub vendor: Option<String>,
+    pub amount_msat: Option<MSat>,
+}
+
+/// Sub-structure for 'next_period' in 'fetchinvoice'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FetchInvoiceNextPeriod {
+    pub counter: u64,
+    pub starttime: u64,
+    pub endtime: u64,
+    pub paywindow_start: u64,
+    pub paywindow_end: u64,
+}
+
+/// 'fetchinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FetchInvoice {
+    pub invoice: String,
+    pub changes: FetchInvoiceChanges,
+    pub next_period: Option<FetchInvoiceNextPeriod>,
+}
+
+/// 'sendinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendInvoice {
+    pub label: String,
+    pub description: Option<String>,
+    pub payment_hash: String,
+    pub status: String,
+    pub expires_at: u64,
+    pub amount_msat: Option<MSat>,
+    pub bolt12: Option<String>,
+    pub pay_index: Option<u64>,
+    pub amount_received_msat: Option<MSat>,
+    pub paid_at: Option<u64>,
+    pub payment_preimage: Option<String>,
+    pub created_index: Option<u64>,
+    pub updated_index: Option<u64>,
+}
+
+/// 'invoicerequest' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InvoiceRequest {
+    pub invreq_id: String,
+    pub active: bool,
+    pub single_use: bool,
+    pub bolt12: String,
+    pub used: bool,
+    pub label: Option<String>,
+}
+
+/// 'listinvoicerequests' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListInvoiceRequests {
+    pub invoicerequests: Vec<InvoiceRequest>,
+}
+
+/// 'disableinvoicerequest' command
+pub type DisableInvoiceRequest = InvoiceRequest;
+
+/// Sub-structure for 'fallbacks' in a decoded bolt11 invoice
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedFallback {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub addr: Option<String>,
+    pub hex: String,
+}
+
+/// Sub-structure for the hops of 'routes' in a decoded bolt11 invoice
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedRouteHop {
+    pub pubkey: String,
+    pub short_channel_id: String,
+    pub fee_base_msat: MSat,
+    pub fee_proportional_millionths: u32,
+    pub cltv_expiry_delta: u32,
+}
+
+/// Sub-structure for 'extra' in a decoded bolt11 invoice
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedExtra {
+    pub tag: String,
+    pub data: String,
+}
+
+/// Sub-structure for a bolt11 invoice in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedBolt11 {
+    pub valid: bool,
+    pub currency: String,
+    pub created_at: u64,
+    pub expiry: u64,
+    pub payee: String,
+    pub amount_msat: Option<MSat>,
+    pub payment_hash: String,
+    pub signature: String,
+    pub description: Option<String>,
+    pub description_hash: Option<String>,
+    pub min_final_cltv_expiry: u64,
+    pub payment_secret: Option<String>,
+    pub features: Option<String>,
+    pub payment_metadata: Option<String>,
+    pub fallbacks: Option<Vec<DecodedFallback>>,
+    pub routes: Option<Vec<Vec<DecodedRouteHop>>>,
+    pub extra: Option<Vec<DecodedExtra>>,
+}
+
+/// Sub-structure for the offer fields of a decoded bolt12 string
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedOfferFields {
+    pub offer_id: Option<String>,
+    pub offer_chains: Option<Vec<String>>,
+    pub offer_metadata: Option<String>,
+    pub offer_currency: Option<String>,
+    pub currency_minor_unit: Option<u32>,
+    pub offer_amount: Option<u64>,
+    pub offer_amount_msat: Option<MSat>,
+    pub offer_description: Option<String>,
+    pub offer_issuer: Option<String>,
+    pub offer_features: Option<String>,
+    pub offer_absolute_expiry: Option<u64>,
+    pub offer_quantity_max: Option<u64>,
+    pub offer_paths: Option<Vec<serde_json::Value>>,
+    pub offer_node_id: Option<String>,
+    pub offer_recurrence: Option<serde_json::Value>,
+    pub warning_unknown_offer_currency: Option<String>,
+    pub warning_missing_offer_node_id: Option<String>,
+    pub warning_invalid_offer_description: Option<String>,
+    pub warning_missing_offer_description: Option<String>,
+    pub warning_invalid_offer_currency: Option<String>,
+    pub warning_invalid_offer_issuer: Option<String>,
+}
+
+/// Sub-structure for the invoice request fields of a decoded bolt12 string
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedInvreqFields {
+    pub invreq_metadata: Option<String>,
+    pub invreq_payer_id: Option<String>,
+    pub invreq_chain: Option<String>,
+    pub invreq_amount_msat: Option<MSat>,
+    pub invreq_features: Option<String>,
+    pub invreq_quantity: Option<u64>,
+    pub invreq_payer_note: Option<String>,
+    pub invreq_paths: Option<Vec<serde_json::Value>>,
+    pub invreq_recurrence_counter: Option<u32>,
+    pub invreq_recurrence_start: Option<u32>,
+    pub warning_invalid_invreq_payer_note: Option<String>,
+    pub warning_missing_invreq_metadata: Option<String>,
+    pub warning_missing_invreq_payer_id: Option<String>,
+}
+
+/// Sub-structure for a bolt12 offer in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedOffer {
+    pub valid: bool,
+    #[serde(flatten)]
+    pub offer: DecodedOfferFields,
+}
+
+/// Sub-structure for a bolt12 invoice request in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedInvoiceRequest {
+    pub valid: bool,
+    #[serde(flatten)]
+    pub offer: DecodedOfferFields,
+    #[serde(flatten)]
+    pub invreq: DecodedInvreqFields,
+    pub signature: Option<String>,
+    pub warning_missing_invoice_request_signature: Option<String>,
+    pub warning_invalid_invoice_request_signature: Option<String>,
+}
+
+/// Sub-structure for a bolt12 invoice in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedInvoice {
+    pub valid: bool,
+    #[serde(flatten)]
+    pub offer: DecodedOfferFields,
+    #[serde(flatten)]
+    pub invreq: DecodedInvreqFields,
+    pub invoice_paths: Option<Vec<serde_json::Value>>,
+    pub invoice_created_at: Option<u64>,
+    pub invoice_relative_expiry: Option<u32>,
+    pub invoice_payment_hash: Option<String>,
+    pub invoice_amount_msat: Option<MSat>,
+    pub invoice_fallbacks: Option<Vec<serde_json::Value>>,
+    pub invoice_features: Option<String>,
+    pub invoice_node_id: Option<String>,
+    pub invoice_recurrence_basetime: Option<u64>,
+    pub signature: Option<String>,
+    pub warning_missing_invoice_paths: Option<String>,
+    pub warning_missing_invoice_blindedpay: Option<String>,
+    pub warning_missing_invoice_created_at: Option<String>,
+    pub warning_missing_invoice_payment_hash: Option<String>,
+    pub warning_missing_invoice_amount: Option<String>,
+    pub warning_missing_invoice_recurrence_basetime: Option<String>,
+    pub warning_missing_invoice_node_id: Option<String>,
+    pub warning_missing_invoice_signature: Option<String>,
+    pub warning_invalid_invoice_signature: Option<String>,
+}
+
+/// Sub-structure for 'restrictions' in a decoded rune
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedRestriction {
+    pub alternatives: Vec<String>,
+    pub summary: String,
+}
+
+/// Sub-structure for a rune in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedRune {
+    pub valid: bool,
+    pub string: Option<String>,
+    pub unique_id: Option<String>,
+    pub version: Option<String>,
+    #[serde(default)]
+    pub restrictions: Vec<DecodedRestriction>,
+    pub warning_rune_invalid_utf8: Option<String>,
+}
+
+/// Sub-structure for an emergency-recover blob in 'decode'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DecodedEmergencyRecover {
+    pub valid: bool,
+    pub decrypted: Option<String>,
+}
+
+/// 'decode' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Decoded {
+    #[serde(rename = "bolt11 invoice")]
+    Bolt11(Box<DecodedBolt11>),
+    #[serde(rename = "bolt12 offer")]
+    Bolt12Offer(Box<DecodedOffer>),
+    #[serde(rename = "bolt12 invoice_request")]
+    Bolt12InvoiceRequest(Box<DecodedInvoiceRequest>),
+    #[serde(rename = "bolt12 invoice")]
+    Bolt12Invoice(Box<DecodedInvoice>),
+    #[serde(rename = "rune")]
+    Rune(Box<DecodedRune>),
+    #[serde(rename = "emergency recover")]
+    EmergencyRecover(Box<DecodedEmergencyRecover>),
+    /// a type of string added after this version of the crate
+    #[serde(other)]
+    Unknown,
+}
+
+impl Decoded {
+    /// Returns whether lightningd considers the decoded string valid
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Decoded::Bolt11(decoded) => decoded.valid,
+            Decoded::Bolt12Offer(decoded) => decoded.valid,
+            Decoded::Bolt12InvoiceRequest(decoded) => decoded.valid,
+            Decoded::Bolt12Invoice(decoded) => decoded.valid,
+            Decoded::Rune(decoded) => decoded.valid,
+            Decoded::EmergencyRecover(decoded) => decoded.valid,
+            Decoded::Unknown => false,
+        }
+    }
+}
+
+/// Sub-structure for 'reservations' in 'fundpsbt', 'utxopsbt', 'reserveinputs' and 'unreserveinputs'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Reservation {
+    pub txid: String,
+    pub vout: u32,
+    pub was_reserved: bool,
+    pub reserved: bool,
+    pub reserved_to_block: Option<u32>,
+}
+
+/// 'fundpsbt' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundPsbt {
+    pub psbt: Psbt,
+    pub feerate_per_kw: u32,
+    pub estimated_final_weight: u32,
+    pub excess_msat: MSat,
+    pub change_outnum: Option<u32>,
+    pub reservations: Option<Vec<Reservation>>,
+}
+
+/// 'utxopsbt' command
+pub type UtxoPsbt = FundPsbt;
+
+/// 'reserveinputs' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReserveInputs {
+    pub reservations: Vec<Reservation>,
+}
+
+/// 'unreserveinputs' command
+pub type UnreserveInputs = ReserveInputs;
+
+/// 'signpsbt' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignPsbt {
+    pub signed_psbt: Psbt,
+}
+
+/// 'sendpsbt' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendPsbt {
+    pub tx: String,
+    pub txid: String,
+}
+
+/// 'txprepare' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxPrepare {
+    pub psbt: Psbt,
+    pub unsigned_tx: String,
+    pub txid: String,
+}
+
+/// 'txsend' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxSend {
+    pub psbt: Psbt,
+    pub tx: String,
+    pub txid: String,
+}
+
+/// 'txdiscard' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TxDiscard {
+    pub unsigned_tx: String,
+    pub txid: String,
+}
+
+/// Sub-structure for 'inputs' in 'listtransactions'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionInput {
+    pub txid: String,
+    pub index: u32,
+    pub sequence: u32,
+}
+
+/// Sub-structure for 'outputs' in 'listtransactions'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionOutput {
+    pub index: u32,
+    pub amount_msat: MSat,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pub_key: String,
+}
+
+/// Sub-structure for 'transactions' in 'listtransactions'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Transaction {
+    pub hash: String,
+    pub rawtx: String,
+    pub blockheight: u32,
+    pub txindex: u32,
+    pub locktime: u32,
+    pub version: u32,
+    pub inputs: Vec<TransactionInput>,
+    pub outputs: Vec<TransactionOutput>,
+}
+
+/// 'listtransactions' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListTransactions {
+    pub transactions: Vec<Transaction>,
+}
+
+/// Sub-structure for 'addresses' in 'listaddresses'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListAddressesAddress {
+    pub keyidx: u64,
+    pub bech32: Option<String>,
+    pub p2tr: Option<String>,
+}
+
+/// 'listaddresses' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListAddresses {
+    pub addresses: Vec<ListAddressesAddress>,
+}
+
+/// 'multiwithdraw' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiWithdraw {
+    pub tx: String,
+    pub txid: String,
+}
+
+/// Sub-structure for 'channel_ids' in 'multifundchannel'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiFundChannelId {
+    pub id: String,
+    pub outnum: u32,
+    pub channel_id: String,
+    pub channel_type: Option<ChannelType>,
+    pub close_to: Option<String>,
+}
+
+/// Sub-structure for 'error' in 'multifundchannel' failures
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiFundChannelError {
+    pub code: i32,
+    pub message: String,
+}
+
+/// Sub-structure for 'failed' in 'multifundchannel'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiFundChannelFailure {
+    pub id: String,
+    pub method: String,
+    pub error: MultiFundChannelError,
+}
+
+/// 'multifundchannel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiFundChannel {
+    pub tx: String,
+    pub txid: String,
+    pub channel_ids: Vec<MultiFundChannelId>,
+    #[serde(default)]
+    pub failed: Vec<MultiFundChannelFailure>,
+}
+
+/// 'fundchannel_start' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundChannelStart {
+    pub funding_address: String,
+    pub scriptpubkey: String,
+    pub channel_type: Option<ChannelType>,
+    pub close_to: Option<String>,
+    pub warning_usage: Option<String>,
+    pub mindepth: Option<u32>,
+}
+
+/// 'fundchannel_complete' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundChannelComplete {
+    pub channel_id: String,
+    pub commitments_secured: bool,
+}
+
+/// 'fundchannel_cancel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundChannelCancel {
+    pub cancelled: String,
+}
+
+/// 'openchannel_init' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenChannelInit {
+    pub channel_id: String,
+    pub psbt: Psbt,
+    pub channel_type: Option<ChannelType>,
+    pub commitments_secured: bool,
+    pub funding_serial: u64,
+    pub requires_confirmed_inputs: Option<bool>,
+}
+
+/// 'openchannel_update' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenChannelUpdate {
+    pub channel_id: String,
+    pub psbt: Psbt,
+    pub channel_type: Option<ChannelType>,
+    pub commitments_secured: bool,
+    pub funding_outnum: u32,
+    pub close_to: Option<String>,
+    pub requires_confirmed_inputs: Option<bool>,
+}
+
+/// 'openchannel_signed' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenChannelSigned {
+    pub channel_id: String,
+    pub tx: String,
+    pub txid: String,
+}
+
+/// 'openchannel_bump' command
+pub type OpenChannelBump = OpenChannelInit;
+
+/// 'openchannel_abort' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenChannelAbort {
+    pub channel_id: String,
+    pub channel_canceled: bool,
+    pub reason: String,
+}
+
+/// 'splice_init' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpliceInit {
+    pub psbt: Psbt,
+}
+
+/// 'splice_update' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpliceUpdate {
+    pub psbt: Psbt,
+    pub commitments_secured: bool,
+    pub signatures_secured: Option<bool>,
+}
+
+/// 'splice_signed' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpliceSigned {
+    pub tx: String,
+    pub txid: String,
+    pub outnum: Option<u32>,
+    pub psbt: Option<Psbt>,
+}
+
+/// 'datastore' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Datastore {
+    pub key: Vec<String>,
+    pub generation: Option<u64>,
+    pub hex: Option<String>,
+    pub string: Option<String>,
+}
+
+/// 'deldatastore' command
+pub type DelDatastore = Datastore;
+
+/// 'listdatastore' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListDatastore {
+    pub datastore: Vec<Datastore>,
+}
+
+/// Sub-structure for 'datastoreusage' in 'datastoreusage'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatastoreUsageEntry {
+    pub key: String,
+    pub total_bytes: u64,
+}
+
+/// 'datastoreusage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatastoreUsage {
+    pub datastoreusage: DatastoreUsageEntry,
+}
+
+/// 'createrune' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateRune {
+    pub rune: String,
+    pub unique_id: String,
+    pub warning_unrestricted_rune: Option<String>,
+}
+
+/// Sub-structure for 'alternatives' in 'showrunes'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuneAlternative {
+    pub fieldname: String,
+    pub value: String,
+    pub condition: String,
+    pub english: String,
+}
+
+/// Sub-structure for 'restrictions' in 'showrunes'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuneRestriction {
+    pub alternatives: Vec<RuneAlternative>,
+    pub english: String,
+}
+
+/// Sub-structure for 'runes' in 'showrunes'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShowRunesRune {
+    pub rune: String,
+    pub unique_id: String,
+    pub restrictions: Vec<RuneRestriction>,
+    pub restrictions_as_english: String,
+    pub stored: Option<bool>,
+    pub blacklisted: Option<bool>,
+    pub last_used: Option<f64>,
+    pub our_rune: Option<bool>,
+}
+
+/// 'showrunes' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShowRunes {
+    pub runes: Vec<ShowRunesRune>,
+}
+
+/// Sub-structure for 'blacklist' in 'blacklistrune'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BlacklistRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// 'blacklistrune' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BlacklistRune {
+    pub blacklist: Vec<BlacklistRange>,
+}
+
+/// 'checkrune' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CheckRune {
+    pub valid: bool,
+}
+
+/// Sub-structure for 'forwards' in 'listforwards'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Forward {
+    pub created_index: Option<u64>,
+    pub updated_index: Option<u64>,
+    pub in_channel: String,
+    pub in_htlc_id: Option<u64>,
+    pub in_msat: MSat,
+    pub status: ForwardStatus,
+    pub received_time: f64,
+    pub out_channel: Option<String>,
+    pub out_htlc_id: Option<u64>,
+    pub style: Option<String>,
+    pub fee_msat: Option<MSat>,
+    pub out_msat: Option<MSat>,
+    pub resolved_time: Option<f64>,
+    pub failcode: Option<u32>,
+    pub failreason: Option<String>,
+}
+
+/// 'listforwards' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListForwards {
+    pub forwards: Vec<Forward>,
+}
+
+/// Sub-structure for 'htlcs' in 'listhtlcs'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListHtlcsHtlc {
+    pub created_index: Option<u64>,
+    pub updated_index: Option<u64>,
+    pub short_channel_id: String,
+    pub id: u64,
+    pub expiry: u32,
+    pub amount_msat: MSat,
+    pub direction: String,
+    pub payment_hash: String,
+    pub state: String,
+}
+
+/// 'listhtlcs' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListHtlcs {
+    pub htlcs: Vec<ListHtlcsHtlc>,
+}
+
+/// Sub-structure for 'channels' in 'setchannel'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetChannelChannel {
+    pub peer_id: String,
+    pub channel_id: String,
+    pub short_channel_id: Option<String>,
+    pub fee_base_msat: MSat,
+    pub fee_proportional_millionths: u32,
+    pub ignore_fee_limits: Option<bool>,
+    pub minimum_htlc_out_msat: MSat,
+    pub warning_htlcmin_too_low: Option<String>,
+    pub maximum_htlc_out_msat: MSat,
+    pub warning_htlcmax_too_high: Option<String>,
+}
+
+/// 'setchannel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetChannel {
+    pub channels: Vec<SetChannelChannel>,
+}
+
+/// Sub-structure for 'invoices' in 'wait'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaitInvoices {
+    pub status: Option<String>,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub bolt11: Option<String>,
+    pub bolt12: Option<String>,
+}
+
+/// Sub-structure for 'forwards' in 'wait'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaitForwards {
+    pub status: Option<ForwardStatus>,
+    pub in_channel: Option<String>,
+    pub in_htlc_id: Option<u64>,
+    pub in_msat: Option<MSat>,
+    pub out_channel: Option<String>,
+}
+
+/// Sub-structure for 'sendpays' in 'wait'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaitSendpays {
+    pub status: Option<String>,
+    pub partid: Option<u64>,
+    pub groupid: Option<u64>,
+    pub payment_hash: Option<String>,
+}
+
+/// 'wait' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Wait {
+    pub subsystem: WaitSubsystem,
+    pub created: Option<u64>,
+    pub updated: Option<u64>,
+    pub deleted: Option<u64>,
+    pub invoices: Option<WaitInvoices>,
+    pub forwards: Option<WaitForwards>,
+    pub sendpays: Option<WaitSendpays>,
+}
+
+impl Wait {
+    /// The value reached by the index {indexname}
+    pub fn index(&self, indexname: WaitIndex) -> Option<u64> {
+        match indexname {
+            WaitIndex::Created => self.created,
+            WaitIndex::Updated => self.updated,
+            WaitIndex::Deleted => self.deleted,
+        }
+    }
+}
+
+/// 'signmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignMessage {
+    pub signature: Hex,
+    pub recid: Hex,
+    pub zbase: ZBase32,
+}
+
+/// 'checkmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CheckMessage {
+    pub pubkey: String,
+    pub verified: bool,
+}
+
+/// 'signinvoice' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignInvoice {
+    pub bolt11: String,
+}
+
+/// 'makesecret' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MakeSecret {
+    pub secret: Hex,
+}
+
+/// 'getemergencyrecoverdata' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetEmergencyRecoverData {
+    pub filedata: Hex,
+}
+
+/// 'emergencyrecover' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmergencyRecover {
+    pub stubs: Vec<String>,
+}
+
+/// 'recoverchannel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecoverChannel {
+    pub stubs: Vec<String>,
+}
+
+/// 'staticbackup' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StaticBackup {
+    pub scb: Vec<Hex>,
+}
+
+/// Sub-structure for 'config' in 'setconfig'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetConfigConfig {
+    pub config: String,
+    #[serde(flatten)]
+    pub value: Config,
+}
+
+/// 'setconfig' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetConfig {
+    pub config: SetConfigConfig,
+}
+
+/// Sub-structure for 'plugins' in 'plugin'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PluginStatus {
+    pub name: String,
+    pub active: bool,
+    pub dynamic: bool,
+}
+
+/// 'plugin' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Plugin {
+    pub command: PluginSubcommand,
+    pub plugins: Option<Vec<PluginStatus>>,
+    pub result: Option<String>,
+}
+
+/// 'sendcustommsg' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendCustomMsg {
+    pub status: String,
+}
+
+/// 'sendonionmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendOnionMessage {}
+
+/// 'injectonionmessage' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InjectOnionMessage {}
+
+/// Sub-structure for 'path' in 'getroutes'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetRoutesHop {
+    pub short_channel_id_dir: String,
+    pub next_node_id: String,
+    pub amount_msat: MSat,
+    pub delay: u32,
+}
+
+/// Sub-structure for 'routes' in 'getroutes'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetRoutesRoute {
+    pub probability_ppm: u64,
+    pub amount_msat: MSat,
+    pub final_cltv: Option<u32>,
+    pub path: Vec<GetRoutesHop>,
+}
+
+impl GetRoutesRoute {
+    /// The route in the form expected by 'sendpay', failing on a hop whose direction
+    /// is not 0 or 1
+    pub fn sendpay_route(&self) -> Result<Vec<RouteItem>, Error> {
+        self.path
+            .iter()
+            .map(|hop| {
+                let (channel, direction) = match hop.short_channel_id_dir.split_once('/') {
+                    Some((channel, "0")) => (channel, Some(0)),
+                    Some((channel, "1")) => (channel, Some(1)),
+                    Some((_, direction)) => {
+                        return Err(<serde_json::Error as de::Error>::invalid_value(
+                            de::Unexpected::Str(direction),
+                            &"a direction of 0 or 1",
+                        )
+                        .into())
+                    }
+                    None => (hop.short_channel_id_dir.as_str(), None),
+                };
+                Ok(RouteItem {
+                    id: hop.next_node_id.clone(),
+                    channel: channel.to_owned(),
+                    direction,
+                    amount_msat: hop.amount_msat,
+                    delay: hop.delay.into(),
+                    style: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// 'getroutes' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetRoutes {
+    pub probability_ppm: u64,
+    pub routes: Vec<GetRoutesRoute>,
+}
+
+/// Sub-structure for 'created_channels' in 'askrene-listlayers'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneCreatedChannel {
+    pub source: String,
+    pub destination: String,
+    pub short_channel_id: String,
+    pub capacity_msat: MSat,
+}
+
+/// Sub-structure for 'channel_updates' in 'askrene-listlayers'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneChannelUpdate {
+    pub short_channel_id_dir: String,
+    pub enabled: Option<bool>,
+    pub htlc_minimum_msat: Option<MSat>,
+    pub htlc_maximum_msat: Option<MSat>,
+    pub fee_base_msat: Option<MSat>,
+    pub fee_proportional_millionths: Option<u32>,
+    pub cltv_expiry_delta: Option<u16>,
+}
+
+/// Sub-structure for 'constraints' in 'askrene-listlayers' and 'askrene-inform-channel'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneConstraint {
+    pub short_channel_id_dir: String,
+    pub layer: Option<String>,
+    pub timestamp: Option<u64>,
+    pub maximum_msat: Option<MSat>,
+    pub minimum_msat: Option<MSat>,
+}
+
+/// Sub-structure for 'biases' in 'askrene-listlayers'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneBias {
+    pub short_channel_id_dir: String,
+    pub bias: i64,
+    pub description: Option<String>,
+}
+
+/// Sub-structure for 'layers' in 'askrene-listlayers' and 'askrene-create-layer'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneLayer {
+    pub layer: String,
+    pub persistent: Option<bool>,
+    #[serde(default)]
+    pub disabled_nodes: Vec<String>,
+    #[serde(default)]
+    pub created_channels: Vec<AskreneCreatedChannel>,
+    #[serde(default)]
+    pub channel_updates: Vec<AskreneChannelUpdate>,
+    #[serde(default)]
+    pub constraints: Vec<AskreneConstraint>,
+    #[serde(default)]
+    pub biases: Vec<AskreneBias>,
+}
+
+/// 'askrene-create-layer' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneCreateLayer {
+    pub layers: Vec<AskreneLayer>,
+}
+
+/// 'askrene-inform-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneInformChannel {
+    pub constraints: Vec<AskreneConstraint>,
+}
+
+/// 'askrene-update-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneUpdateChannel {}
+
+/// 'askrene-create-channel' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneCreateChannel {}
+
+/// 'askrene-listlayers' command
+pub type AskreneListLayers = AskreneCreateLayer;
+
+/// 'askrene-age' command
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AskreneAge {
+    pub layer: String,
+    pub num_removed: u64,
+}
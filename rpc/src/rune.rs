@@ -0,0 +1,377 @@
+//! Runes, the bearer tokens granting access to the lightningd commands
+//!
+//! A rune is the base64url encoding of a 32 bytes authentication code
+//! followed by its restrictions, e.g. `=1&method=listfunds|method=getinfo`.
+//! The restrictions are separated by `&`, and each one is satisfied when
+//! one of its alternatives, separated by `|`, is. `\` escapes these
+//! characters in the values.
+//!
+//! The restrictions passed to `LightningRPC::createrune` are built with
+//! `Restriction` and `Alternative`, and a rune can be decoded offline with
+//! `Rune::from_str`, without checking its authentication code.
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::encoding::base64url_decode;
+
+/// Length of the authentication code at the start of a rune
+const AUTHCODE_LEN: usize = 32;
+
+/// Error returned when a string is not a valid rune
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuneError {
+    /// The string is not valid base64url
+    InvalidBase64,
+    /// The rune is shorter than its authentication code
+    TooShort,
+    /// The restriction can not be parsed
+    InvalidRestriction(String),
+}
+
+impl fmt::Display for RuneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RuneError::InvalidBase64 => write!(f, "rune is not valid base64url"),
+            RuneError::TooShort => write!(f, "rune is too short"),
+            RuneError::InvalidRestriction(ref restriction) => {
+                write!(f, "invalid rune restriction '{}'", restriction)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuneError {}
+
+/// Comparison between a field and the value of an alternative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    /// `!` the field is missing
+    Missing,
+    /// `=` the field is equal to the value
+    Equal,
+    /// `/` the field is not equal to the value
+    NotEqual,
+    /// `^` the field starts with the value
+    StartsWith,
+    /// `$` the field ends with the value
+    EndsWith,
+    /// `~` the field contains the value
+    Contains,
+    /// `<` the field is an integer less than the value
+    IntLess,
+    /// `>` the field is an integer greater than the value
+    IntGreater,
+    /// `{` the field sorts before the value
+    LexLess,
+    /// `}` the field sorts after the value
+    LexGreater,
+    /// `#` always true, the value is a comment
+    Comment,
+}
+
+impl Condition {
+    const ALL: [Condition; 11] = [
+        Condition::Missing,
+        Condition::Equal,
+        Condition::NotEqual,
+        Condition::StartsWith,
+        Condition::EndsWith,
+        Condition::Contains,
+        Condition::IntLess,
+        Condition::IntGreater,
+        Condition::LexLess,
+        Condition::LexGreater,
+        Condition::Comment,
+    ];
+
+    /// The character of the condition in a rune
+    pub fn as_char(self) -> char {
+        match self {
+            Condition::Missing => '!',
+            Condition::Equal => '=',
+            Condition::NotEqual => '/',
+            Condition::StartsWith => '^',
+            Condition::EndsWith => '$',
+            Condition::Contains => '~',
+            Condition::IntLess => '<',
+            Condition::IntGreater => '>',
+            Condition::LexLess => '{',
+            Condition::LexGreater => '}',
+            Condition::Comment => '#',
+        }
+    }
+
+    fn from_char(c: char) -> Option<Condition> {
+        Condition::ALL
+            .iter()
+            .copied()
+            .find(|condition| condition.as_char() == c)
+    }
+}
+
+/// A single check of a restriction, e.g. `method=listfunds`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternative {
+    /// the field checked, made of letters, digits and `_`
+    pub field: String,
+    /// the comparison made
+    pub condition: Condition,
+    /// the value compared to, unescaped
+    pub value: String,
+}
+
+impl Alternative {
+    /// The {field} compared with {value} by {condition}
+    pub fn new(field: &str, condition: Condition, value: &str) -> Alternative {
+        Alternative {
+            field: field.to_owned(),
+            condition,
+            value: value.to_owned(),
+        }
+    }
+
+    /// The command is {method}
+    pub fn method(method: &str) -> Alternative {
+        Alternative::new("method", Condition::Equal, method)
+    }
+
+    /// The parameter {name} of the command compared with {value} by {condition}
+    pub fn pname(name: &str, condition: Condition, value: &str) -> Alternative {
+        Alternative::new(&format!("pname{}", name), condition, value)
+    }
+
+    /// The command is run before the UNIX timestamp {time}
+    pub fn time_before(time: u64) -> Alternative {
+        Alternative::new("time", Condition::IntLess, &time.to_string())
+    }
+
+    /// The command is run by the node {id}
+    pub fn id(id: &str) -> Alternative {
+        Alternative::new("id", Condition::Equal, id)
+    }
+
+    /// At most {per_minute} commands are run per minute
+    pub fn rate(per_minute: u32) -> Alternative {
+        Alternative::new("rate", Condition::Equal, &per_minute.to_string())
+    }
+
+    fn parse(s: &str) -> Result<Alternative, RuneError> {
+        let (start, c) = s
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '_')
+            .ok_or_else(|| RuneError::InvalidRestriction(s.to_owned()))?;
+        let condition =
+            Condition::from_char(c).ok_or_else(|| RuneError::InvalidRestriction(s.to_owned()))?;
+        Ok(Alternative {
+            field: s[..start].to_owned(),
+            condition,
+            value: s[start + c.len_utf8()..].to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Alternative {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.field, self.condition.as_char())?;
+        for c in self.value.chars() {
+            if matches!(c, '\\' | '|' | '&') {
+                write!(f, "\\")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// A restriction of a rune, satisfied when one of its alternatives is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restriction(pub Vec<Alternative>);
+
+impl Restriction {
+    /// A restriction with the single {alternative}
+    pub fn new(alternative: Alternative) -> Restriction {
+        Restriction(vec![alternative])
+    }
+
+    /// Also accept {alternative}
+    pub fn or(mut self, alternative: Alternative) -> Restriction {
+        self.0.push(alternative);
+        self
+    }
+
+    /// The alternatives, escaped as in a rune, as expected by `createrune`
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl From<Alternative> for Restriction {
+    fn from(alternative: Alternative) -> Restriction {
+        Restriction::new(alternative)
+    }
+}
+
+impl fmt::Display for Restriction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, alternative) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "|")?;
+            }
+            write!(f, "{}", alternative)?;
+        }
+        Ok(())
+    }
+}
+
+/// A rune decoded offline, its authentication code is not checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rune {
+    /// the authentication code
+    pub authcode: [u8; AUTHCODE_LEN],
+    /// the unique id, from the leading `=id` restriction of the runes made by lightningd
+    pub unique_id: Option<String>,
+    /// the version, from a leading `=id-version` restriction
+    pub version: Option<String>,
+    /// the restrictions, without the unique id one
+    pub restrictions: Vec<Restriction>,
+}
+
+impl FromStr for Rune {
+    type Err = RuneError;
+
+    fn from_str(s: &str) -> Result<Rune, RuneError> {
+        let bytes = base64url_decode(s).ok_or(RuneError::InvalidBase64)?;
+        if bytes.len() < AUTHCODE_LEN {
+            return Err(RuneError::TooShort);
+        }
+        let (authcode, restrictions) = bytes.split_at(AUTHCODE_LEN);
+        let restrictions = String::from_utf8(restrictions.to_vec()).map_err(|err| {
+            RuneError::InvalidRestriction(String::from_utf8_lossy(err.as_bytes()).into_owned())
+        })?;
+
+        let mut rune = Rune {
+            authcode: authcode.try_into().expect("split at the authcode length"),
+            unique_id: None,
+            version: None,
+            restrictions: parse_restrictions(&restrictions)?,
+        };
+        let unique_id = match rune
+            .restrictions
+            .first()
+            .map(|restriction| &restriction.0[..])
+        {
+            Some([alternative])
+                if alternative.field.is_empty() && alternative.condition == Condition::Equal =>
+            {
+                alternative.value.clone()
+            }
+            _ => return Ok(rune),
+        };
+        rune.restrictions.remove(0);
+        match unique_id.split_once('-') {
+            Some((id, version)) => {
+                rune.unique_id = Some(id.to_owned());
+                rune.version = Some(version.to_owned());
+            }
+            None => rune.unique_id = Some(unique_id),
+        }
+        Ok(rune)
+    }
+}
+
+/// Split the {restrictions} of a rune on the unescaped `&` and `|`
+fn parse_restrictions(restrictions: &str) -> Result<Vec<Restriction>, RuneError> {
+    let mut parsed = Vec::new();
+    if restrictions.is_empty() {
+        return Ok(parsed);
+    }
+    let mut alternatives = Vec::new();
+    let mut current = String::new();
+    let mut chars = restrictions.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.push(chars.next().unwrap_or('\\')),
+            '|' => alternatives.push(Alternative::parse(&std::mem::take(&mut current))?),
+            '&' => {
+                alternatives.push(Alternative::parse(&std::mem::take(&mut current))?);
+                parsed.push(Restriction(std::mem::take(&mut alternatives)));
+            }
+            c => current.push(c),
+        }
+    }
+    alternatives.push(Alternative::parse(&current)?);
+    parsed.push(Restriction(alternatives));
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Alternative, Condition, Restriction, Rune, RuneError};
+    use crate::encoding::base64_encode;
+
+    /// Encode {bytes} as base64url without padding, as lightningd does
+    fn base64url(bytes: &[u8]) -> String {
+        base64_encode(bytes)
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_")
+    }
+
+    #[test]
+    fn build_restrictions() {
+        let restrictions = [
+            Restriction::new(Alternative::method("listfunds")).or(Alternative::method("getinfo")),
+            Alternative::pname("amount_msat", Condition::IntLess, "1000").into(),
+            Alternative::time_before(1700000000).into(),
+            Alternative::new("note", Condition::Comment, "a|b&c\\d").into(),
+        ];
+        let strings: Vec<String> = restrictions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            strings,
+            [
+                "method=listfunds|method=getinfo",
+                "pnameamount_msat<1000",
+                "time<1700000000",
+                "note#a\\|b\\&c\\\\d"
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_rune() {
+        let mut bytes = vec![7u8; 32];
+        bytes.extend_from_slice(b"=3-1&method=listfunds|method=getinfo&note#a\\|b\\&c");
+        let rune: Rune = base64url(&bytes).parse().unwrap();
+        assert_eq!(rune.authcode, [7u8; 32]);
+        assert_eq!(rune.unique_id.as_deref(), Some("3"));
+        assert_eq!(rune.version.as_deref(), Some("1"));
+        assert_eq!(
+            rune.restrictions,
+            [
+                Restriction::new(Alternative::method("listfunds"))
+                    .or(Alternative::method("getinfo")),
+                Alternative::new("note", Condition::Comment, "a|b&c").into(),
+            ]
+        );
+
+        let unrestricted: Rune = base64url(&[0u8; 32]).parse().unwrap();
+        assert!(unrestricted.unique_id.is_none());
+        assert!(unrestricted.restrictions.is_empty());
+        // older runes kept the padding
+        let padded: Rune = format!("{}=", base64url(&[0u8; 32])).parse().unwrap();
+        assert_eq!(padded, unrestricted);
+
+        assert_eq!("!!".parse::<Rune>(), Err(RuneError::InvalidBase64));
+        assert_eq!(
+            base64url(&[0u8; 8]).parse::<Rune>(),
+            Err(RuneError::TooShort)
+        );
+        let mut bytes = vec![0u8; 32];
+        bytes.extend_from_slice(b"method");
+        assert_eq!(
+            base64url(&bytes).parse::<Rune>(),
+            Err(RuneError::InvalidRestriction("method".to_owned()))
+        );
+    }
+}
@@ -5,6 +5,8 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::str::FromStr;
 
+use crate::encoding::{base64_decode, base64_encode, hex_decode, zbase32_decode};
+
 /// Sub-structure for route in 'pay', 'getroute' and 'sendpay'
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RouteItem {
@@ -16,6 +18,82 @@ pub struct RouteItem {
     pub style: Option<String>,
 }
 
+/// Sub-structure for the hops of 'routehints' in 'keysend'
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RouteHintHop {
+    pub id: String,
+    pub short_channel_id: String,
+    pub fee_base_msat: MSat,
+    pub fee_proportional_millionths: u32,
+    pub cltv_expiry_delta: u16,
+}
+
+/// Status of a forwarded HTLC, in 'listforwards'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardStatus {
+    /// still in flight
+    Offered,
+    /// fulfilled by the next hop
+    Settled,
+    /// failed by this node before it was forwarded
+    LocalFailed,
+    /// failed by the next hop
+    Failed,
+}
+
+/// Subsystem watched by 'wait'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitSubsystem {
+    /// the invoices, as listed by 'listinvoices'
+    Invoices,
+    /// the forwarded HTLCs, as listed by 'listforwards'
+    Forwards,
+    /// the payment attempts, as listed by 'listsendpays'
+    Sendpays,
+}
+
+/// Index watched by 'wait'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitIndex {
+    /// increased by every new entry
+    Created,
+    /// increased by every change to an entry
+    Updated,
+    /// increased by every deleted entry
+    Deleted,
+}
+
+/// Subcommand of 'plugin'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginSubcommand {
+    /// start a plugin
+    Start,
+    /// stop a plugin
+    Stop,
+    /// start all the plugins of a directory
+    Startdir,
+    /// start the plugins of the plugin directories that are not running yet
+    Rescan,
+    /// list the running plugins
+    List,
+}
+
+/// What was learnt about a channel, in 'askrene-inform-channel'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AskreneInform {
+    /// the channel could not forward the amount
+    Constrained,
+    /// the channel forwarded the amount, but the payment failed further along the route
+    Unconstrained,
+    /// the payment went through, the amount is no longer in the channel
+    Succeeded,
+}
+
 /// Type-safe millisatoshi wrapper
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct MSat(pub u64);
@@ -86,11 +164,328 @@ impl fmt::Display for MSat {
     }
 }
 
+/// Error returned when a string is not a base64 encoded PSBT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PsbtError {
+    /// The string is not valid base64
+    InvalidBase64,
+    /// The decoded bytes do not start with the PSBT magic bytes
+    InvalidMagic,
+}
+
+impl fmt::Display for PsbtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PsbtError::InvalidBase64 => write!(f, "PSBT is not valid base64"),
+            PsbtError::InvalidMagic => write!(f, "PSBT does not start with the magic bytes"),
+        }
+    }
+}
+
+impl std::error::Error for PsbtError {}
+
+/// Magic bytes at the start of every PSBT (BIP 174)
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+/// A Partially Signed Bitcoin Transaction, encoded in base64 as lightningd does.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Psbt(String);
+
+impl Psbt {
+    /// Wrap a base64 encoded PSBT, checking that it is valid base64 and
+    /// that it starts with the PSBT magic bytes.
+    pub fn from_base64(s: &str) -> Result<Psbt, PsbtError> {
+        let bytes = base64_decode(s).ok_or(PsbtError::InvalidBase64)?;
+        if !bytes.starts_with(PSBT_MAGIC) {
+            return Err(PsbtError::InvalidMagic);
+        }
+        Ok(Psbt(s.to_owned()))
+    }
+
+    /// Encode a serialized PSBT in base64
+    pub fn from_bytes(bytes: &[u8]) -> Result<Psbt, PsbtError> {
+        if !bytes.starts_with(PSBT_MAGIC) {
+            return Err(PsbtError::InvalidMagic);
+        }
+        Ok(Psbt(base64_encode(bytes)))
+    }
+
+    /// The PSBT encoded in base64
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The serialized PSBT
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PsbtError> {
+        base64_decode(&self.0).ok_or(PsbtError::InvalidBase64)
+    }
+}
+
+impl FromStr for Psbt {
+    type Err = PsbtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Psbt::from_base64(s)
+    }
+}
+
+impl fmt::Debug for Psbt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Psbt({})", self.0)
+    }
+}
+
+impl fmt::Display for Psbt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for Psbt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'d> Deserialize<'d> for Psbt {
+    fn deserialize<D>(deserializer: D) -> Result<Psbt, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Psbt::from_base64(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Error returned when a string is not valid hex or zbase32
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The string is not an even number of hex digits
+    InvalidHex,
+    /// The string contains characters outside of the zbase32 alphabet
+    InvalidZBase32,
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EncodingError::InvalidHex => write!(f, "string is not valid hex"),
+            EncodingError::InvalidZBase32 => write!(f, "string is not valid zbase32"),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// Bytes encoded in lowercase hex, e.g. a signature or a secret
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Hex(String);
+
+impl Hex {
+    /// Wrap a hex string, checking that it is an even number of hex digits
+    pub fn from_hex(s: &str) -> Result<Hex, EncodingError> {
+        let s = s.to_ascii_lowercase();
+        hex_decode(&s).ok_or(EncodingError::InvalidHex)?;
+        Ok(Hex(s))
+    }
+
+    /// Encode {bytes} in hex
+    pub fn from_bytes(bytes: &[u8]) -> Hex {
+        Hex(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// The hex string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The decoded bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EncodingError> {
+        hex_decode(&self.0).ok_or(EncodingError::InvalidHex)
+    }
+}
+
+impl FromStr for Hex {
+    type Err = EncodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Hex::from_hex(s)
+    }
+}
+
+impl fmt::Debug for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Hex({})", self.0)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for Hex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'d> Deserialize<'d> for Hex {
+    fn deserialize<D>(deserializer: D) -> Result<Hex, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Hex::from_hex(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Bytes encoded in zbase32, as the signatures of 'signmessage'
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ZBase32(String);
+
+impl ZBase32 {
+    /// Wrap a zbase32 string, checking that it only uses the zbase32 alphabet
+    pub fn from_zbase32(s: &str) -> Result<ZBase32, EncodingError> {
+        zbase32_decode(s).ok_or(EncodingError::InvalidZBase32)?;
+        Ok(ZBase32(s.to_owned()))
+    }
+
+    /// The zbase32 string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The decoded bytes, without the trailing bits that do not fill a byte
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EncodingError> {
+        zbase32_decode(&self.0).ok_or(EncodingError::InvalidZBase32)
+    }
+}
+
+impl FromStr for ZBase32 {
+    type Err = EncodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ZBase32::from_zbase32(s)
+    }
+}
+
+impl fmt::Debug for ZBase32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZBase32({})", self.0)
+    }
+}
+
+impl fmt::Display for ZBase32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for ZBase32 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'d> Deserialize<'d> for ZBase32 {
+    fn deserialize<D>(deserializer: D) -> Result<ZBase32, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ZBase32::from_zbase32(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Error returned when a custom message can not be sent to a peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomMessageError {
+    /// The message type is even, which the peer must understand (BOLT 1)
+    EvenType(u16),
+    /// The message does not fit in a lightning message (65535 bytes, with the type)
+    TooLarge(usize),
+}
+
+impl fmt::Display for CustomMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CustomMessageError::EvenType(msg_type) => {
+                write!(f, "custom message type {} is even", msg_type)
+            }
+            CustomMessageError::TooLarge(len) => {
+                write!(f, "custom message of {} bytes is too large", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CustomMessageError {}
+
+/// Maximum length of a lightning message, including its type
+const MAX_MESSAGE_LEN: usize = 65535;
+
+/// A peer message of an odd {msg_type}, which peers not knowing it ignore,
+/// see 'sendcustommsg'
+///
+/// lightningd also refuses to send the types it handles itself (e.g. 1, the warnings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomMessage {
+    msg_type: u16,
+    payload: Vec<u8>,
+}
+
+impl CustomMessage {
+    /// The message of {msg_type} with {payload}, e.g. encoded with the
+    /// `ToWire` trait of the fundamentals crate
+    pub fn new(msg_type: u16, payload: &[u8]) -> Result<CustomMessage, CustomMessageError> {
+        if msg_type & 1 == 0 {
+            return Err(CustomMessageError::EvenType(msg_type));
+        }
+        if payload.len() + 2 > MAX_MESSAGE_LEN {
+            return Err(CustomMessageError::TooLarge(payload.len() + 2));
+        }
+        Ok(CustomMessage {
+            msg_type,
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// The message type
+    pub fn msg_type(&self) -> u16 {
+        self.msg_type
+    }
+
+    /// The message payload, after the type
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// The message as sent on the wire: the big endian type then the payload
+    pub fn to_hex(&self) -> Hex {
+        let mut bytes = self.msg_type.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.payload);
+        Hex::from_bytes(&bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
 
-    use crate::types::MSat;
+    use crate::types::{
+        CustomMessage, CustomMessageError, EncodingError, Hex, MSat, Psbt, PsbtError, ZBase32,
+    };
 
     #[test]
     fn test_msat() {
@@ -98,4 +493,48 @@ mod tests {
         let v2: MSat = serde_json::from_value(json!("3msat")).unwrap();
         assert_eq!(v1, v2);
     }
+
+    #[test]
+    fn test_psbt() {
+        let psbt: Psbt = serde_json::from_value(json!("cHNidP8BAAoCAAAAAAAAAAAAAAA=")).unwrap();
+        let bytes = psbt.to_bytes().unwrap();
+        assert_eq!(&bytes[..5], b"psbt\xff");
+        assert_eq!(Psbt::from_bytes(&bytes).unwrap(), psbt);
+        assert_eq!(Psbt::from_base64("aGVsbG8="), Err(PsbtError::InvalidMagic));
+        assert_eq!(Psbt::from_base64("cHNi!P8="), Err(PsbtError::InvalidBase64));
+        assert!(serde_json::from_value::<Psbt>(json!("nope")).is_err());
+    }
+
+    #[test]
+    fn test_hex_and_zbase32() {
+        let hex: Hex = serde_json::from_value(json!("00FFa0")).unwrap();
+        assert_eq!(hex.to_bytes().unwrap(), [0x00, 0xff, 0xa0]);
+        assert_eq!(Hex::from_bytes(&[0x00, 0xff, 0xa0]), hex);
+        assert_eq!(Hex::from_hex("abc"), Err(EncodingError::InvalidHex));
+        assert_eq!(Hex::from_hex("zz"), Err(EncodingError::InvalidHex));
+
+        // "yy" encodes the single zero byte, the two remaining bits are dropped
+        let zbase: ZBase32 = serde_json::from_value(json!("yy")).unwrap();
+        assert_eq!(zbase.to_bytes().unwrap(), [0]);
+        let zbase = ZBase32::from_zbase32("9999").unwrap();
+        assert_eq!(zbase.to_bytes().unwrap(), [0xff, 0xff]);
+        assert_eq!(
+            ZBase32::from_zbase32("l0v"),
+            Err(EncodingError::InvalidZBase32)
+        );
+    }
+
+    #[test]
+    fn test_custom_message() {
+        let message = CustomMessage::new(0x8001, b"hi").unwrap();
+        assert_eq!(message.to_hex().as_str(), "80016869");
+        assert_eq!(
+            CustomMessage::new(0x8000, b"hi"),
+            Err(CustomMessageError::EvenType(0x8000))
+        );
+        assert_eq!(
+            CustomMessage::new(0x8001, &[0; 65534]),
+            Err(CustomMessageError::TooLarge(65536))
+        );
+    }
 }
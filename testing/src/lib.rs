@@ -1,4 +1,5 @@
 pub mod cln;
+pub mod mock;
 
 pub mod prelude {
     pub use port_selector as port;
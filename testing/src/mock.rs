@@ -0,0 +1,252 @@
+//! Mock lightningd socket server, to run tests without a regtest stack.
+//!
+//! The server answers the requests with the responses stored in a fixture
+//! file, in the same order, and records the requests that do not match.
+//! In recording mode it forwards the requests to a real lightningd
+//! socket, recording the traffic so it can be saved as a fixture file.
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+use serde_json::{de::IoRead, json, Deserializer, StreamDeserializer, Value};
+use tempfile::TempDir;
+
+#[cfg(feature = "async")]
+use clightningrpc::r#async::LightningRPC;
+#[cfg(not(feature = "async"))]
+use clightningrpc::LightningRPC;
+pub use clightningrpc_common::transport::Exchange;
+
+type Messages = StreamDeserializer<'static, IoRead<BufReader<UnixStream>>, Value>;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// socket of the real lightningd, when recording
+    upstream: Option<PathBuf>,
+    /// exchanges not served yet
+    fixtures: Mutex<VecDeque<Exchange>>,
+    /// exchanges recorded from the real lightningd
+    recorded: Mutex<Vec<Exchange>>,
+    /// description of the requests that did not match the fixtures
+    unexpected: Mutex<Vec<String>>,
+    stop: AtomicBool,
+}
+
+impl State {
+    /// Find the response to the request in the fixtures
+    fn replay(&self, method: &str, params: &Value) -> Value {
+        let mut fixtures = lock(&self.fixtures);
+        let matches = fixtures
+            .front()
+            .is_some_and(|exchange| exchange.method == method && &exchange.params == params);
+        if matches {
+            if let Some(exchange) = fixtures.pop_front() {
+                return serde_json::to_value(exchange.response).unwrap_or_default();
+            }
+        }
+        let message = format!("unexpected request `{method}` with params {params}");
+        lock(&self.unexpected).push(message.clone());
+        json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -1, "message": format!("mock lightningd: {message}") },
+        })
+    }
+}
+
+/// A mock of the lightningd RPC socket, listening on a temporary UNIX socket.
+#[derive(Debug)]
+pub struct MockServer {
+    sockpath: PathBuf,
+    state: Arc<State>,
+    // keep the directory of the socket alive
+    _dir: TempDir,
+}
+
+impl MockServer {
+    /// Serve the `exchanges` in order.
+    pub fn new(exchanges: Vec<Exchange>) -> anyhow::Result<Self> {
+        let state = State {
+            fixtures: Mutex::new(exchanges.into()),
+            ..State::default()
+        };
+        Self::start(state)
+    }
+
+    /// Serve the exchanges stored in the fixture file at `path`, as
+    /// written by `MockServer::save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        let exchanges: Vec<Exchange> = serde_json::from_reader(file)?;
+        Self::new(exchanges)
+    }
+
+    /// Forward all the requests to the lightningd socket at `upstream`,
+    /// recording the requests together with their responses.
+    pub fn record<P: AsRef<Path>>(upstream: P) -> anyhow::Result<Self> {
+        let state = State {
+            upstream: Some(upstream.as_ref().to_path_buf()),
+            ..State::default()
+        };
+        Self::start(state)
+    }
+
+    fn start(state: State) -> anyhow::Result<Self> {
+        let dir = tempfile::tempdir()?;
+        let sockpath = dir.path().join("lightning-rpc");
+        let listener = UnixListener::bind(&sockpath)?;
+        let state = Arc::new(state);
+        let server = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server.stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let server = server.clone();
+                thread::spawn(move || {
+                    if let Err(err) = serve(stream, &server) {
+                        log::debug!("mock lightningd connection closed: {err}");
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            sockpath,
+            state,
+            _dir: dir,
+        })
+    }
+
+    /// Path of the socket to connect to
+    pub fn sockpath(&self) -> &Path {
+        &self.sockpath
+    }
+
+    /// Create a new RPC interface connected to the mock
+    pub fn rpc(&self) -> LightningRPC {
+        LightningRPC::new(&self.sockpath)
+    }
+
+    /// Exchanges recorded so far in recording mode
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        lock(&self.state.recorded).clone()
+    }
+
+    /// Write the exchanges recorded so far to the fixture file at `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut file, &*lock(&self.state.recorded))?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Check that all the requests matched the fixtures, and that all the
+    /// fixtures were served.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let mut errors = Vec::new();
+        let unexpected = lock(&self.state.unexpected);
+        if let Some(request) = unexpected.first() {
+            errors.push(format!(
+                "{} unexpected requests, first: {request}",
+                unexpected.len()
+            ));
+        }
+        let fixtures = lock(&self.state.fixtures);
+        if let Some(exchange) = fixtures.front() {
+            errors.push(format!(
+                "{} requests never received, first: `{}`",
+                fixtures.len(),
+                exchange.method
+            ));
+        }
+        if !errors.is_empty() {
+            anyhow::bail!(errors.join("; "));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.state.stop.store(true, Ordering::SeqCst);
+        // wake up the server waiting for a connection
+        let _ = UnixStream::connect(&self.sockpath);
+    }
+}
+
+/// Answer the requests received on a connection until it is closed
+fn serve(stream: UnixStream, state: &State) -> anyhow::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut upstream = match &state.upstream {
+        Some(sockpath) => Some(Upstream::connect(sockpath)?),
+        None => None,
+    };
+    let requests = Deserializer::from_reader(BufReader::new(stream)).into_iter::<Value>();
+    for request in requests {
+        let request = request?;
+        let method = request["method"].as_str().unwrap_or_default().to_owned();
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let mut response = match upstream.as_mut() {
+            Some(upstream) => {
+                let response = upstream.forward(&request, &mut writer)?;
+                lock(&state.recorded).push(Exchange {
+                    method,
+                    params,
+                    response: serde_json::from_value(response.clone())?,
+                });
+                response
+            }
+            None => state.replay(&method, &params),
+        };
+        response["id"] = request.get("id").cloned().unwrap_or(Value::Null);
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Connection to the real lightningd socket
+struct Upstream {
+    writer: UnixStream,
+    messages: Messages,
+}
+
+impl Upstream {
+    fn connect(sockpath: &Path) -> anyhow::Result<Self> {
+        let stream = UnixStream::connect(sockpath)?;
+        let writer = stream.try_clone()?;
+        Ok(Self {
+            writer,
+            messages: Deserializer::from_reader(BufReader::new(stream)).into_iter(),
+        })
+    }
+
+    /// Send the request to lightningd and wait for its response, passing
+    /// the notifications received in the meantime to `client`.
+    fn forward(&mut self, request: &Value, client: &mut UnixStream) -> anyhow::Result<Value> {
+        serde_json::to_writer(&mut self.writer, request)?;
+        self.writer.flush()?;
+        loop {
+            let Some(message) = self.messages.next() else {
+                anyhow::bail!("connection closed by lightningd");
+            };
+            let message = message?;
+            if message.get("id").is_some() && message.get("id") == request.get("id") {
+                return Ok(message);
+            }
+            serde_json::to_writer(&mut *client, &message)?;
+            client.flush()?;
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::sync::Once;
 
+#[cfg(all(test, not(feature = "async")))]
+mod test_mock;
 #[cfg(all(test, not(feature = "async")))]
 mod test_plugin;
 #[cfg(all(test, not(feature = "async")))]
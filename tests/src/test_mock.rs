@@ -0,0 +1,56 @@
+//! RPC testing against the mock lightningd, without a regtest stack
+use clightning_testing::mock::MockServer;
+use clightning_testing::prelude::clightningrpc;
+use clightningrpc::types::MSat;
+
+fn fixture(name: &str) -> String {
+    format!("{}/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+#[allow(deprecated)]
+fn replay_getinfo_and_listfunds() {
+    let mock = MockServer::load(fixture("getinfo_listfunds.json")).unwrap();
+    let rpc = mock.rpc();
+    let info = rpc.getinfo().unwrap();
+    assert_eq!(info.network, "regtest");
+    assert_eq!(info.fees_collected_msat, MSat(0));
+    let funds = rpc.listfunds().unwrap();
+    assert!(funds.outputs.is_empty());
+    mock.verify().unwrap();
+}
+
+#[test]
+#[allow(deprecated)]
+fn fail_on_unexpected_request() {
+    let mock = MockServer::load(fixture("getinfo_listfunds.json")).unwrap();
+    let rpc = mock.rpc();
+    assert!(rpc.listfunds().is_err());
+    // the unexpected request does not consume the expected one
+    assert_eq!(rpc.getinfo().unwrap().network, "regtest");
+    let err = mock.verify().unwrap_err().to_string();
+    assert!(err.contains("1 unexpected requests"), "{err}");
+    assert!(err.contains("1 requests never received"), "{err}");
+}
+
+#[test]
+#[allow(deprecated)]
+fn record_and_replay() {
+    let upstream = MockServer::load(fixture("getinfo_listfunds.json")).unwrap();
+    let recorder = MockServer::record(upstream.sockpath()).unwrap();
+    let rpc = recorder.rpc();
+    let info = rpc.getinfo().unwrap();
+    rpc.listfunds().unwrap();
+    upstream.verify().unwrap();
+
+    let dir = clightning_testing::prelude::tempfile::tempdir().unwrap();
+    let path = dir.path().join("recorded.json");
+    recorder.save(&path).unwrap();
+    assert_eq!(recorder.exchanges().len(), 2);
+
+    let mock = MockServer::load(&path).unwrap();
+    let rpc = mock.rpc();
+    assert_eq!(rpc.getinfo().unwrap().id, info.id);
+    rpc.listfunds().unwrap();
+    mock.verify().unwrap();
+}